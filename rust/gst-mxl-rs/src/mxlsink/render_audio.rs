@@ -53,7 +53,7 @@ pub(crate) fn audio(
         src.len() / (audio_state.flow_def.channel_count as usize * bytes_per_sample);
     let gst_pts = buffer.pts().ok_or(gst::FlowError::Error)?;
     trace!("GST BUFFER PTS: {:#?}", gst_pts);
-    let mxl_now = state.instance.get_time();
+    let mxl_now = state.instance.get_time().as_nanos();
 
     let initial = audio_state.initial_time.get_or_insert(InitialTime {
         mxl_pts_offset: mxl_now - gst_now.nseconds(),
@@ -95,7 +95,7 @@ pub(crate) fn audio(
 
         let latency_ns = samples_to_ns(audio_state.latency, &sample_rate);
         let mut pts = mxl_pts + latency_ns;
-        let mxl_now = state.instance.get_time();
+        let mxl_now = state.instance.get_time().as_nanos();
         if pts < mxl_now {
             let diff_ns = mxl_now - pts;
             let diff_samples = ns_to_samples(diff_ns, &sample_rate);
@@ -106,7 +106,7 @@ pub(crate) fn audio(
         }
         let mxl_index = state
             .instance
-            .timestamp_to_index(pts, &sample_rate)
+            .timestamp_to_index(pts.into(), &sample_rate)
             .map_err(|_| gst::FlowError::Error)?;
 
         let data = WriteSampleData {
@@ -225,8 +225,9 @@ fn write_buffer(engine: &mut AudioEngine, data: WriteSampleData) -> Result<(), g
     let pts = engine
         .instance
         .index_to_timestamp(mxl_index, &sample_rate)
-        .map_err(|_| gst::FlowError::Error)?;
-    let mxl_now = engine.instance.get_time();
+        .map_err(|_| gst::FlowError::Error)?
+        .as_nanos();
+    let mxl_now = engine.instance.get_time().as_nanos();
     if pts > mxl_now {
         trace!("Audio sleeping: {:#?}", Duration::from_nanos(pts - mxl_now));
         let (lock, cvar) = &*engine.sleep_flag;
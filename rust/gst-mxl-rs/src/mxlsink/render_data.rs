@@ -33,7 +33,7 @@ pub(crate) fn data(
 
     let clock = element.clock().ok_or(gst::FlowError::Error)?;
     let gst_now = clock.time();
-    let mxl_now = state.instance.get_time();
+    let mxl_now = state.instance.get_time().as_nanos();
     let initial = data_state.initial_time.get_or_insert(InitialTime {
         mxl_pts_offset: mxl_now - gst_now.nseconds(),
     });
@@ -54,7 +54,7 @@ pub(crate) fn data(
     trace!("DATA mapped PTS: {:#?}", pts);
     let mxl_index = state
         .instance
-        .timestamp_to_index(pts, &grain_rate)
+        .timestamp_to_index(pts.into(), &grain_rate)
         .map_err(|_| gst::FlowError::Error)?;
     trace!("DATA mapped mxl_index from pts: {:#?}", mxl_index);
     commit_buffer(buffer, data_state, mxl_index)?;
@@ -114,11 +114,12 @@ fn write_buffer(engine: &mut DataEngine, data: WriteGrainData) -> Result<(), gst
     let copy_len = std::cmp::min(payload.len(), data.buf.len());
     payload[..copy_len].copy_from_slice(&data.buf[..copy_len]);
     let total_slices = access.total_slices();
-    let mxl_now = engine.instance.get_time();
+    let mxl_now = engine.instance.get_time().as_nanos();
     let pts = engine
         .instance
         .index_to_timestamp(data.index, &engine.grain_rate)
-        .map_err(|_| gst::FlowError::Error)?;
+        .map_err(|_| gst::FlowError::Error)?
+        .as_nanos();
 
     if pts > mxl_now {
         trace!("Data sleeping: {:#?}", Duration::from_nanos(pts - mxl_now));
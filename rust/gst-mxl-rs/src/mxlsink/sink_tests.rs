@@ -66,7 +66,6 @@ mod tests {
                 framerate.numer() / framerate.denom()
             ),
             tags,
-            format: "urn:x-nmos:format:video".into(),
             label: format!(
                 "MXL Test Flow, 1080p{}",
                 framerate.numer() / framerate.denom()
@@ -21,7 +21,7 @@ use gst_audio::AudioInfo;
 use gstreamer as gst;
 use gstreamer_audio as gst_audio;
 use mxl::{
-    FlowConfigInfo, GrainWriter, MxlInstance, Rational, SamplesWriter,
+    CreatedFlow, FlowConfigInfo, GrainWriter, MxlInstance, Rational, SamplesWriter,
     flowdef::{
         Component, FlowDef, FlowDefAudio, FlowDefData, FlowDefDetails, FlowDefVideo, InterlaceMode,
         Rate,
@@ -192,7 +192,6 @@ pub(crate) fn init_state_with_video(
             framerate.numer() / framerate.denom()
         ),
         tags,
-        format: "urn:x-nmos:format:video".into(),
         label: format!(
             "MXL Test Flow, {}p{}",
             height,
@@ -204,13 +203,12 @@ pub(crate) fn init_state_with_video(
     };
     let instance = &state.instance;
 
-    let (flow_writer, flow, is_created) = instance
-        .create_flow_writer(
-            serde_json::to_string(&flow_def)
-                .map_err(|e| gst::loggable_error!(CAT, "Failed to convert: {}", e))?
-                .as_str(),
-            None,
-        )
+    let CreatedFlow {
+        writer: flow_writer,
+        config: flow,
+        was_created: is_created,
+    } = instance
+        .create_flow_writer_from_def(&flow_def, None)
         .map_err(|e| gst::loggable_error!(CAT, "Failed to create flow writer: {}", e))?;
     if !is_created {
         return Err(gst::loggable_error!(
@@ -283,7 +281,6 @@ pub(crate) fn init_state_with_audio(
         id: Uuid::parse_str(flow_id)
             .map_err(|e| gst::loggable_error!(CAT, "Flow ID is invalid: {}", e))?,
         description: "MXL Audio Flow".into(),
-        format: "urn:x-nmos:format:audio".into(),
         tags,
         label: "MXL Audio Flow".into(),
         media_type: "audio/float32".to_string(),
@@ -291,14 +288,13 @@ pub(crate) fn init_state_with_audio(
         details: FlowDefDetails::Audio(flow_def_details.clone()),
     };
 
-    let (flow_writer, flow, is_created) = state
+    let CreatedFlow {
+        writer: flow_writer,
+        config: flow,
+        was_created: is_created,
+    } = state
         .instance
-        .create_flow_writer(
-            serde_json::to_string(&flow_def)
-                .map_err(|e| gst::loggable_error!(CAT, "Failed to convert: {}", e))?
-                .as_str(),
-            None,
-        )
+        .create_flow_writer_from_def(&flow_def, None)
         .map_err(|e| gst::loggable_error!(CAT, "Failed to create flow writer: {}", e))?;
     let writer = Some(
         flow_writer
@@ -372,7 +368,6 @@ pub(crate) fn init_state_with_data(
             .map_err(|e| gst::loggable_error!(CAT, "Flow ID is invalid: {}", e))?,
         description: "MXL SMPTE 291 data flow".into(),
         tags,
-        format: "urn:x-nmos:format:data".into(),
         label: "MXL data flow".into(),
         parents: vec![],
         media_type: "video/smpte291".into(),
@@ -380,13 +375,12 @@ pub(crate) fn init_state_with_data(
     };
     let instance = &state.instance;
 
-    let (flow_writer, flow, is_created) = instance
-        .create_flow_writer(
-            serde_json::to_string(&flow_def)
-                .map_err(|e| gst::loggable_error!(CAT, "Failed to convert: {}", e))?
-                .as_str(),
-            None,
-        )
+    let CreatedFlow {
+        writer: flow_writer,
+        config: flow,
+        was_created: is_created,
+    } = instance
+        .create_flow_writer_from_def(&flow_def, None)
         .map_err(|e| gst::loggable_error!(CAT, "Failed to create flow writer: {}", e))?;
     if !is_created {
         return Err(gst::loggable_error!(
@@ -122,7 +122,7 @@ fn audio_state_init(
 ) {
     if !audio_state.is_initialized {
         *initial_info = InitialTime {
-            mxl_index: instance.get_time(),
+            mxl_index: instance.get_time().as_nanos(),
             gst_time: ts_gst,
         };
         audio_state.index = reader_info.runtime.head_index().saturating_sub(batch);
@@ -138,7 +138,7 @@ fn resync_state(
     audio_state: &mut AudioState,
 ) {
     initial_info.gst_time = ts_gst;
-    initial_info.mxl_index = instance.get_time();
+    initial_info.mxl_index = instance.get_time().as_nanos();
     audio_state.batch_counter = 0;
     audio_state.next_discont = true;
 }
@@ -235,11 +235,7 @@ fn build_buffer(
     next_discont: bool,
     interleaved: Vec<u8>,
 ) -> Result<Buffer, gst::FlowError> {
-    let mut buf_size = 0;
-    for i in 0..samples.num_of_channels() {
-        let (a, b) = samples.channel_data(i).map_err(|_| gst::FlowError::Error)?;
-        buf_size += a.len() + b.len();
-    }
+    let buf_size: usize = samples.channels().map(|(a, b)| a.len() + b.len()).sum();
 
     let mut buffer = gst::Buffer::with_size(buf_size).map_err(|_| gst::FlowError::Error)?;
 
@@ -296,6 +292,6 @@ fn compute_batch_duration(
     let read_head_timestamp = instance
         .index_to_timestamp(index, sample_rate)
         .map_err(|_| gst::FlowError::Error)?;
-    let read_batch_duration = next_head_timestamp - read_head_timestamp;
+    let read_batch_duration = next_head_timestamp.as_nanos() - read_head_timestamp.as_nanos();
     Ok(read_batch_duration)
 }
@@ -23,8 +23,7 @@ pub struct Opts {
     pub flow_id: String,
 
     /// The number of samples to be read in one open samples call. Is only valid for "continuous"
-    /// flows. If not specified, the value provided by the writer will be used if available, or this
-    /// will more or less fit 10 ms as a fallback.
+    /// flows. If not specified, the batch size recommended by the flow configuration is used.
     #[arg(long)]
     pub sample_batch_size: Option<u64>,
 }
@@ -46,7 +45,6 @@ fn main() -> Result<(), mxl::Error> {
         read_grains(mxl_instance, reader.to_grain_reader()?, flow_info)
     } else {
         read_samples(
-            mxl_instance,
             reader.to_samples_reader()?,
             flow_info,
             opts.sample_batch_size,
@@ -56,16 +54,17 @@ fn main() -> Result<(), mxl::Error> {
 
 fn read_grains(
     mxl_instance: mxl::MxlInstance,
-    reader: mxl::GrainReader,
+    mut reader: mxl::GrainReader,
     flow_info: mxl::FlowInfo,
 ) -> Result<(), mxl::Error> {
     let rate = flow_info.config.common().grain_rate()?;
     let current_index = mxl_instance.get_current_index(&rate);
+    reader.set_default_timeout(READ_TIMEOUT);
 
     info!("Grain rate: {}/{}", rate.numerator, rate.denominator);
 
     for index in current_index.. {
-        let grain_data = reader.get_complete_grain(index, READ_TIMEOUT)?;
+        let grain_data = reader.get_complete_grain_default(index)?;
         info!(
             "Index: {index} Grain data len: {:?}",
             grain_data.payload.len()
@@ -76,7 +75,6 @@ fn read_grains(
 }
 
 fn read_samples(
-    mxl_instance: mxl::MxlInstance,
     reader: mxl::SamplesReader,
     flow_info: mxl::FlowInfo,
     batch_size: Option<u64>,
@@ -84,75 +82,40 @@ fn read_samples(
     let common_flow_info = flow_info.config.common();
     let flow_id = common_flow_info.id().to_string();
     let sample_rate = common_flow_info.sample_rate()?;
+    let recommended_batch_size = common_flow_info.recommended_read_batch();
     let batch_size = if let Some(batch_size) = batch_size {
-        if common_flow_info.max_commit_batch_size_hint() != 0
-            && batch_size != common_flow_info.max_commit_batch_size_hint() as u64
-        {
+        if batch_size as usize != recommended_batch_size {
             warn!(
-                "Writer batch size is set to {}, but sample batch size is provided, using the \
-                 latter.",
-                common_flow_info.max_commit_batch_size_hint()
+                "Recommended batch size is {recommended_batch_size}, but sample batch size is \
+                 provided, using the latter."
             );
         }
         batch_size as usize
-    } else if common_flow_info.max_commit_batch_size_hint() == 0 {
-        let batch_size = (sample_rate.numerator / (100 * sample_rate.denominator)) as usize;
-        warn!(
-            "Writer batch size not available, using fallback value of {}.",
-            batch_size
-        );
-        batch_size
     } else {
-        common_flow_info.max_commit_batch_size_hint() as usize
+        recommended_batch_size
     };
-    let mut read_head = reader.get_runtime_info()?.headIndex;
-    let mut read_head_valid_at = mxl_instance.get_time();
+    let mut stream_reader = mxl::SamplesStreamReader::new(reader, batch_size)?;
     info!(
         "Will read from flow \"{flow_id}\" with sample rate {}/{}, using batches of size \
-        {batch_size} samples, first batch ending at index {read_head}.",
-        sample_rate.numerator, sample_rate.denominator
+        {batch_size} samples, first batch ending at index {}.",
+        sample_rate.numerator,
+        sample_rate.denominator,
+        stream_reader.next_index()
     );
     loop {
-        let samples_data = reader.get_samples_non_blocking(read_head, batch_size)?;
+        let read_head = stream_reader.next_index();
+        let samples_data = stream_reader.next_batch(READ_TIMEOUT).inspect_err(|err| {
+            if matches!(err, mxl::Error::Timeout) {
+                warn!("Timeout while waiting for samples at index {}.", read_head);
+            }
+        })?;
         info!(
             "Read samples for {} channel(s) at index {}.",
-            samples_data.num_of_channels(),
+            samples_data.payload.len(),
             read_head
         );
-        if samples_data.num_of_channels() > 0 {
-            let channel_data = samples_data.channel_data(0)?;
-            info!(
-                "Buffer size for channel 0 is ({}, {}).",
-                channel_data.0.len(),
-                channel_data.1.len()
-            );
-        }
-        // TODO:
-        //      Switch to the recently introduced reading mechanism that waits for data to be
-        //      available.
-        // We will just blindly assume that more data will be available when we need them.
-        // This, of course, does not have to be true, because the write batch size may be larger
-        // than our reading batch size.
-        let next_head = read_head + batch_size as u64;
-        let next_head_timestamp = mxl_instance.index_to_timestamp(next_head, &sample_rate)?;
-        let read_head_timestamp = mxl_instance.index_to_timestamp(read_head, &sample_rate)?;
-        let read_batch_duration = next_head_timestamp - read_head_timestamp;
-        let deadline = std::time::Instant::now() + READ_TIMEOUT;
-        loop {
-            read_head_valid_at += read_batch_duration;
-            let sleep_duration =
-                Duration::from_nanos(read_head_valid_at.saturating_sub(mxl_instance.get_time()));
-            info!("Will sleep for {:?}.", sleep_duration);
-            mxl_instance.sleep_for(sleep_duration);
-            if std::time::Instant::now() >= deadline {
-                warn!("Timeout while waiting for samples at index {}.", next_head);
-                return Err(mxl::Error::Timeout);
-            }
-            let available_head = reader.get_runtime_info()?.headIndex;
-            if available_head >= next_head {
-                break;
-            }
+        for (channel, data) in samples_data.payload.iter().enumerate() {
+            info!("Buffer size for channel {channel} is {}.", data.len());
         }
-        read_head = next_head;
     }
 }
@@ -3,10 +3,12 @@
 
 mod common;
 
+use std::time::Duration;
+
 use clap::Parser;
 use tracing::{info, warn};
 
-use mxl::config::get_mxl_so_path;
+use mxl::{RationalExt, config::get_mxl_so_path};
 
 #[derive(Debug, Parser)]
 #[command(version = clap::crate_version!(), author = clap::crate_authors!())]
@@ -24,7 +26,8 @@ pub struct Opts {
     pub grain_or_sample_count: Option<u64>,
 
     /// The number of samples to be written in one open samples call. Is only valid for "continuous"
-    /// flows. If not specified, will more or less fit 10 ms.
+    /// flows. If not specified, the commit batch size hint of the flow is used, or if there is
+    /// none, will more or less fit 10 ms.
     #[arg(long)]
     pub sample_batch_size: Option<u64>,
 }
@@ -42,8 +45,11 @@ fn main() -> Result<(), mxl::Error> {
         ))
     })?;
 
-    let (writer, flow_config_info, was_created) =
-        mxl_instance.create_flow_writer(flow_def.as_str(), None)?;
+    let mxl::CreatedFlow {
+        writer,
+        config: flow_config_info,
+        was_created,
+    } = mxl_instance.create_flow_writer(flow_def.as_str(), None)?;
     if !was_created {
         warn!("Reusing existing flow.");
     }
@@ -79,40 +85,23 @@ pub fn write_grains(
 ) -> Result<(), mxl::Error> {
     let flow_id = flow_config_info.common().id().to_string();
     let grain_rate = flow_config_info.common().grain_rate()?;
-    let mut grain_index = mxl_instance.get_current_index(&grain_rate);
+    let grain_index = mxl_instance.get_current_index(&grain_rate);
     info!(
         "Will write to flow \"{flow_id}\" with grain rate {}/{} starting from index {grain_index}.",
         grain_rate.numerator, grain_rate.denominator
     );
 
-    let mut remaining_grains = grain_count;
-    loop {
-        if let Some(count) = remaining_grains {
-            if count == 0 {
-                break;
+    writer.run_source(
+        grain_index,
+        &grain_rate,
+        grain_count,
+        |grain_index, payload| {
+            for (i, byte) in payload.iter_mut().enumerate() {
+                *byte = ((i as u64 + grain_index) % 256) as u8;
             }
-            remaining_grains = Some(count - 1);
-        }
-
-        let mut grain_writer_access = writer.open_grain(grain_index)?;
-        let total_slices = grain_writer_access.total_slices();
-        let payload = grain_writer_access.payload_mut();
-        let payload_len = payload.len();
-        for (i, byte) in payload.iter_mut().enumerate() {
-            *byte = ((i as u64 + grain_index) % 256) as u8;
-        }
-        grain_writer_access.commit(total_slices)?;
-
-        let timestamp = mxl_instance.index_to_timestamp(grain_index + 1, &grain_rate)?;
-        let sleep_duration = mxl_instance.get_duration_until_index(grain_index + 1, &grain_rate)?;
-        info!(
-            "Finished writing {payload_len} bytes ({total_slices} slices) into grain {grain_index}, will sleep \
-             for {:?} until timestamp {timestamp}.",
-            sleep_duration
-        );
-        grain_index += 1;
-        mxl_instance.sleep_for(sleep_duration);
-    }
+            info!("Writing {} bytes into grain {grain_index}.", payload.len());
+        },
+    )?;
 
     info!("Finished writing requested number of grains, deleting the flow.");
     writer.destroy()?;
@@ -128,52 +117,37 @@ pub fn write_samples(
 ) -> Result<(), mxl::Error> {
     let flow_id = flow_config_info.common().id().to_string();
     let sample_rate = flow_config_info.common().sample_rate()?;
-    let batch_size =
-        batch_size.unwrap_or((sample_rate.numerator / (100 * sample_rate.denominator)) as u64);
-    let mut samples_index = mxl_instance.get_current_index(&sample_rate);
+    let batch_size = match batch_size.or(flow_config_info.common().recommended_write_batch()) {
+        Some(batch_size) => batch_size,
+        None => sample_rate
+            .samples_in(Duration::from_millis(10))
+            .ok_or_else(|| mxl::Error::Other("Invalid sample rate.".to_owned()))?,
+    };
+    let samples_index = mxl_instance.get_current_index(&sample_rate);
     info!(
         "Will write to flow \"{flow_id}\" with sample rate {}/{}, using batches of size {batch_size} samples, first batch ending at index {samples_index}.",
         sample_rate.numerator, sample_rate.denominator
     );
 
-    let mut remaining_samples = sample_count;
-    loop {
-        if let Some(count) = remaining_samples
-            && count == 0
-        {
-            break;
-        }
-        let samples_to_write = u64::min(batch_size, remaining_samples.unwrap_or(u64::MAX));
-        if let Some(count) = remaining_samples {
-            remaining_samples = Some(count.saturating_sub(batch_size));
-        }
-
-        let mut samples_write_access = writer.open_samples(samples_index, batch_size as usize)?;
-        let mut writing_sample_index = samples_index - batch_size + 1;
-        for channel in 0..samples_write_access.channels() {
-            let (data_1, data_2) = samples_write_access.channel_data_mut(channel)?;
-            for sample in data_1.iter_mut() {
-                *sample = (writing_sample_index % 256) as u8;
-                writing_sample_index += 1;
-            }
-            for sample in data_2.iter_mut() {
-                *sample = (writing_sample_index % 256) as u8;
-                writing_sample_index += 1;
+    writer.run_source(
+        samples_index + 1 - batch_size,
+        &sample_rate,
+        batch_size as usize,
+        sample_count,
+        |first_index, samples_write_access| {
+            let mut writing_sample_index = first_index;
+            for channel in 0..samples_write_access.channels() {
+                let Ok((data_1, data_2)) = samples_write_access.channel_data_mut(channel) else {
+                    continue;
+                };
+                for sample in data_1.iter_mut().chain(data_2.iter_mut()) {
+                    *sample = (writing_sample_index % 256) as u8;
+                    writing_sample_index += 1;
+                }
             }
-        }
-        samples_write_access.commit()?;
-
-        let timestamp =
-            mxl_instance.index_to_timestamp(samples_index + batch_size, &sample_rate)?;
-        let sleep_duration =
-            mxl_instance.get_duration_until_index(samples_index + batch_size, &sample_rate)?;
-        info!(
-            "Finished writing {samples_to_write} samples into batch ending with index {samples_index}, will sleep for {:?} until timestamp {timestamp}.",
-            sleep_duration
-        );
-        samples_index += batch_size;
-        mxl_instance.sleep_for(sleep_duration);
-    }
+            info!("Writing batch starting with index {first_index}.");
+        },
+    )?;
 
     info!("Finished writing requested number of samples, deleting the flow.");
     writer.destroy()?;
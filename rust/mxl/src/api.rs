@@ -1,11 +1,14 @@
 // SPDX-FileCopyrightText: 2025 2025 Contributors to the Media eXchange Layer project.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{path::Path, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use mxl_sys::libmxl;
 
-use crate::Result;
+use crate::{Error, Result};
 
 pub type MxlApi = libmxl;
 pub type MxlApiHandle = Arc<MxlApi>;
@@ -15,3 +18,47 @@ pub fn load_api(path_to_so_file: impl AsRef<Path>) -> Result<MxlApiHandle> {
         libmxl::new(path_to_so_file.as_ref().as_os_str())?
     }))
 }
+
+/// Tries to load the MXL library from each of the given paths in order, and returns the first one
+/// that loads. If none does, the returned `Error::Other` lists the error of every attempt.
+pub fn load_api_from_candidates(paths: &[PathBuf]) -> Result<MxlApiHandle> {
+    let mut errors = Vec::with_capacity(paths.len());
+    for path in paths {
+        match load_api(path) {
+            Ok(api) => return Ok(api),
+            Err(error) => errors.push(format!("\"{}\": {error}", path.display())),
+        }
+    }
+    Err(Error::Other(format!(
+        "Failed to load the MXL library from any of the candidates [{}].",
+        errors.join(", ")
+    )))
+}
+
+/// Version of the loaded MXL library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: u16,
+    pub minor: u16,
+    pub bugfix: u16,
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.bugfix)
+    }
+}
+
+/// Queries the version of the loaded MXL library.
+///
+/// `mxlGetVersion` is required to be present when the library is loaded, so a library without it
+/// is already rejected by `load_api` with `Error::LibLoading`.
+pub fn library_version(api: &MxlApi) -> Result<Version> {
+    let mut version = mxl_sys::VersionType::default();
+    unsafe { Error::from_status(api.get_version(&mut version))? };
+    Ok(Version {
+        major: version.major,
+        minor: version.minor,
+        bugfix: version.bugfix,
+    })
+}
@@ -1,10 +1,37 @@
 // SPDX-FileCopyrightText: 2025 2025 Contributors to the Media eXchange Layer project.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::RwLock;
+
 pub type Result<T> = core::result::Result<T, Error>;
 
+static DROP_ERROR_HANDLER: RwLock<fn(&Error)> = RwLock::new(|_| {});
+
+/// Sets the function called with the errors that occur while releasing readers and writers or
+/// canceling grains and samples on drop, which cannot be returned to the caller. These errors are
+/// always logged with `tracing` as well, the handler lets applications without a `tracing`
+/// subscriber report them through their own channel. The default handler does nothing.
+///
+/// The handler is shared by all the threads, and may be called from any of them.
+pub fn set_drop_error_handler(handler: fn(&Error)) {
+    // The lock only guards copying a function pointer, so it cannot be poisoned.
+    *DROP_ERROR_HANDLER
+        .write()
+        .unwrap_or_else(|error| error.into_inner()) = handler;
+}
+
+/// Passes an error that occurred on drop to the handler set with `set_drop_error_handler`.
+pub(crate) fn report_drop_error(error: &Error) {
+    let handler = *DROP_ERROR_HANDLER
+        .read()
+        .unwrap_or_else(|error| error.into_inner());
+    handler(error);
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
+    /// `MXL_ERR_UNKNOWN` or any status without a dedicated variant, e.g. one introduced by a newer
+    /// MXL library. The raw status code is preserved.
     #[error("Unknown error: {0}")]
     Unknown(mxl_sys::Status),
     #[error("Flow not found")]
@@ -27,14 +54,37 @@ pub enum Error {
     #[error("Other error: {0}")]
     Other(String),
 
+    /// The CRC-32 of a grain payload is not the expected one.
+    #[error("Checksum mismatch: expected {expected:#010x}, got {actual:#010x}")]
+    ChecksumMismatch { expected: u32, actual: u32 },
+
+    /// Committing a grain failed with `source`. The grain was then canceled, unless `canceled` is
+    /// false, in which case the cancellation failed as well and the state of the grain is unknown.
+    #[error("Grain commit failed: {source} (grain canceled: {canceled})")]
+    CommitFailed { source: Box<Error>, canceled: bool },
+
     #[error("Null string: {0}")]
     NulString(#[from] std::ffi::NulError),
 
+    /// A string returned by the MXL library, e.g. a flow definition, is not valid UTF-8.
+    #[error("Invalid UTF-8: {0}")]
+    Utf8(#[from] std::string::FromUtf8Error),
+
+    /// A flow id is not a valid UUID.
+    #[error("Invalid flow id: {0}")]
+    InvalidFlowId(#[from] uuid::Error),
+
     #[error("Loading library: {0}")]
     LibLoading(#[from] libloading::Error),
 }
 
 impl Error {
+    /// Maps an MXL status to the matching error.
+    ///
+    /// The status is all the detail there is: the MXL API has no last-error message or similar
+    /// means to describe a failure further, so the variants cannot carry more context than their
+    /// status code. The MXL library logs the details of most failures itself instead, see the
+    /// `MXL_LOG_LEVEL` environment variable.
     pub fn from_status(status: mxl_sys::Status) -> Result<()> {
         match status {
             mxl_sys::MXL_STATUS_OK => Ok(()),
@@ -51,3 +101,25 @@ impl Error {
         }
     }
 }
+
+/// Lets MXL calls be used in functions returning `std::io::Result`. The MXL error is kept as the
+/// inner error, so that its message is preserved.
+impl From<Error> for std::io::Error {
+    fn from(error: Error) -> Self {
+        let kind = match error {
+            Error::Timeout => std::io::ErrorKind::TimedOut,
+            Error::FlowNotFound => std::io::ErrorKind::NotFound,
+            Error::InvalidArg | Error::InvalidFlowId(_) => std::io::ErrorKind::InvalidInput,
+            _ => std::io::ErrorKind::Other,
+        };
+        std::io::Error::new(kind, error)
+    }
+}
+
+/// Lets the infallible conversions be used where a fallible one is expected, e.g. a `Uuid` passed
+/// as flow id to `MxlInstance::create_flow_reader`.
+impl From<std::convert::Infallible> for Error {
+    fn from(infallible: std::convert::Infallible) -> Self {
+        match infallible {}
+    }
+}
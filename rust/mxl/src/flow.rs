@@ -2,12 +2,24 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod flowdef;
+pub mod id;
 pub mod reader;
+mod sdp;
 pub mod writer;
 
+use std::{
+    cmp::Ordering,
+    ops::{Add, Mul, Sub},
+    str::FromStr,
+    time::{Duration, SystemTime},
+};
+
 use uuid::Uuid;
 
-use crate::{Error, Result};
+use crate::{
+    Error, Result, Timestamp,
+    flow::flowdef::{FlowDef, FlowDefDetails, FlowDefVideo, InterlaceMode},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DataFormat {
@@ -20,7 +32,7 @@ pub enum DataFormat {
 impl From<u32> for DataFormat {
     fn from(value: u32) -> Self {
         match value {
-            0 => DataFormat::Unspecified,
+            mxl_sys::MXL_DATA_FORMAT_UNSPECIFIED => DataFormat::Unspecified,
             mxl_sys::MXL_DATA_FORMAT_VIDEO => DataFormat::Video,
             mxl_sys::MXL_DATA_FORMAT_AUDIO => DataFormat::Audio,
             mxl_sys::MXL_DATA_FORMAT_DATA => DataFormat::Data,
@@ -29,6 +41,315 @@ impl From<u32> for DataFormat {
     }
 }
 
+impl DataFormat {
+    /// The matching `mxl_sys::MXL_DATA_FORMAT_*` value.
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            DataFormat::Unspecified => mxl_sys::MXL_DATA_FORMAT_UNSPECIFIED,
+            DataFormat::Video => mxl_sys::MXL_DATA_FORMAT_VIDEO,
+            DataFormat::Audio => mxl_sys::MXL_DATA_FORMAT_AUDIO,
+            DataFormat::Data => mxl_sys::MXL_DATA_FORMAT_DATA,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            DataFormat::Unspecified => "unspecified",
+            DataFormat::Video => "video",
+            DataFormat::Audio => "audio",
+            DataFormat::Data => "data",
+        }
+    }
+}
+
+impl std::fmt::Display for DataFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DataFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "unspecified" => Ok(DataFormat::Unspecified),
+            "video" => Ok(DataFormat::Video),
+            "audio" => Ok(DataFormat::Audio),
+            "data" => Ok(DataFormat::Data),
+            _ => Err(Error::Other(format!("Unknown data format \"{s}\"."))),
+        }
+    }
+}
+
+/// Where the payload of a flow lives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadLocation {
+    Host,
+    /// Device (e.g. GPU) memory.
+    Device,
+    /// A location not known to this version of the bindings.
+    Unknown(u32),
+}
+
+impl From<u32> for PayloadLocation {
+    fn from(value: u32) -> Self {
+        match value {
+            mxl_sys::MXL_PAYLOAD_LOCATION_HOST_MEMORY => PayloadLocation::Host,
+            mxl_sys::MXL_PAYLOAD_LOCATION_DEVICE_MEMORY => PayloadLocation::Device,
+            other => PayloadLocation::Unknown(other),
+        }
+    }
+}
+
+/// Index of the device holding the payload of a flow located in device memory.
+pub type DeviceIndex = u32;
+
+/// Rust-side conveniences for the grain and sample rates expressed as `mxl_sys::Rational`.
+///
+/// All the methods return `None` when the rational cannot represent a rate, e.g. when the
+/// denominator is zero.
+pub trait RationalExt {
+    /// The rate as a floating point number of ticks per second.
+    fn as_f64(&self) -> Option<f64>;
+
+    /// The duration of a single tick (one grain or one sample).
+    fn period(&self) -> Option<Duration>;
+
+    /// The number of whole ticks that fit into the given duration.
+    fn samples_in(&self, duration: Duration) -> Option<u64>;
+
+    /// The duration of `count` ticks, rounded down to the nanosecond. Unlike multiplying the
+    /// `period`, this does not accumulate its rounding error.
+    fn duration_of(&self, count: u64) -> Option<Duration>;
+
+    /// The same rate with numerator and denominator divided by their greatest common divisor.
+    fn reduce(&self) -> Option<mxl_sys::Rational>;
+}
+
+impl RationalExt for mxl_sys::Rational {
+    fn as_f64(&self) -> Option<f64> {
+        if self.denominator == 0 {
+            return None;
+        }
+        Some(self.numerator as f64 / self.denominator as f64)
+    }
+
+    fn period(&self) -> Option<Duration> {
+        if self.numerator <= 0 || self.denominator <= 0 {
+            return None;
+        }
+        let period_ns = self.denominator as u128 * 1_000_000_000u128 / self.numerator as u128;
+        Some(Duration::from_nanos(u64::try_from(period_ns).ok()?))
+    }
+
+    fn samples_in(&self, duration: Duration) -> Option<u64> {
+        if self.numerator < 0 || self.denominator <= 0 {
+            return None;
+        }
+        let samples = duration.as_nanos().checked_mul(self.numerator as u128)?
+            / (self.denominator as u128 * 1_000_000_000u128);
+        u64::try_from(samples).ok()
+    }
+
+    fn duration_of(&self, count: u64) -> Option<Duration> {
+        if self.numerator <= 0 || self.denominator <= 0 {
+            return None;
+        }
+        let duration_ns = (count as u128)
+            .checked_mul(self.denominator as u128 * 1_000_000_000u128)?
+            / self.numerator as u128;
+        Some(Duration::from_nanos(u64::try_from(duration_ns).ok()?))
+    }
+
+    fn reduce(&self) -> Option<mxl_sys::Rational> {
+        if self.denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(
+            self.numerator.unsigned_abs(),
+            self.denominator.unsigned_abs(),
+        );
+        // The divisor is never zero here, because the denominator is not zero.
+        let divisor = divisor as i64;
+        Some(mxl_sys::Rational {
+            numerator: self.numerator / divisor,
+            denominator: self.denominator / divisor,
+        })
+    }
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// A grain or sample rate supporting exact arithmetic, e.g. to convert between 48 kHz audio and
+/// 30000/1001 video rates.
+///
+/// The rate is always kept reduced, with a positive denominator. The arithmetic operators panic if
+/// the reduced result does not fit into `mxl_sys::Rational`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RationalRate {
+    numerator: i64,
+    denominator: i64,
+}
+
+impl RationalRate {
+    /// Largest denominator considered by `from_f64_approx`.
+    const MAX_APPROX_DENOMINATOR: i64 = 1_000_000;
+
+    /// Returns `None` if the denominator is zero.
+    pub fn new(numerator: i64, denominator: i64) -> Option<Self> {
+        Self::from_i128(numerator as i128, denominator as i128)
+    }
+
+    /// Approximates `value` by the last convergent of its continued fraction whose denominator is
+    /// at most one million, so that e.g. `30000.0 / 1001.0` gives back exactly 30000/1001.
+    /// Returns `None` for values that are not finite or do not fit.
+    pub fn from_f64_approx(value: f64) -> Option<Self> {
+        if !value.is_finite() || value.abs() >= i64::MAX as f64 {
+            return None;
+        }
+        // Convergents h/k of the continued fraction, starting with h(-1)/k(-1) = 1/0 and
+        // h(-2)/k(-2) = 0/1.
+        let (mut h, mut h_prev) = (1i64, 0i64);
+        let (mut k, mut k_prev) = (0i64, 1i64);
+        let mut remainder = value;
+        loop {
+            let term = remainder.floor();
+            let term_int = term as i64;
+            let next_h = term_int.checked_mul(h)?.checked_add(h_prev)?;
+            let next_k = term_int.checked_mul(k)?.checked_add(k_prev)?;
+            if next_k > Self::MAX_APPROX_DENOMINATOR {
+                break;
+            }
+            (h_prev, h) = (h, next_h);
+            (k_prev, k) = (k, next_k);
+            let fraction = remainder - term;
+            if (value - h as f64 / k as f64).abs() <= f64::EPSILON * value.abs() || fraction == 0.0
+            {
+                break;
+            }
+            remainder = 1.0 / fraction;
+        }
+        Self::new(h, k)
+    }
+
+    pub fn numerator(&self) -> i64 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i64 {
+        self.denominator
+    }
+
+    pub fn as_rational(&self) -> mxl_sys::Rational {
+        mxl_sys::Rational {
+            numerator: self.numerator,
+            denominator: self.denominator,
+        }
+    }
+
+    /// The number of whole ticks (grains or samples) in the given duration. Saturates at
+    /// `u64::MAX`, and is zero for rates that are not positive.
+    pub fn ticks_per(&self, duration: Duration) -> u64 {
+        if self.numerator <= 0 {
+            return 0;
+        }
+        self.as_rational().samples_in(duration).unwrap_or(u64::MAX)
+    }
+
+    fn from_i128(numerator: i128, denominator: i128) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd_u128(numerator.unsigned_abs(), denominator.unsigned_abs()) as i128;
+        let sign = denominator.signum();
+        Some(Self {
+            numerator: i64::try_from(sign * numerator / divisor).ok()?,
+            denominator: i64::try_from(sign * denominator / divisor).ok()?,
+        })
+    }
+
+    fn from_i128_or_panic(numerator: i128, denominator: i128) -> Self {
+        Self::from_i128(numerator, denominator).expect("Rate arithmetic overflow.")
+    }
+}
+
+fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+impl TryFrom<mxl_sys::Rational> for RationalRate {
+    type Error = Error;
+
+    fn try_from(value: mxl_sys::Rational) -> Result<Self> {
+        Self::new(value.numerator, value.denominator).ok_or_else(|| {
+            Error::Other(format!(
+                "Invalid rate {}/{}.",
+                value.numerator, value.denominator
+            ))
+        })
+    }
+}
+
+impl From<RationalRate> for mxl_sys::Rational {
+    fn from(value: RationalRate) -> Self {
+        value.as_rational()
+    }
+}
+
+impl Add for RationalRate {
+    type Output = RationalRate;
+
+    fn add(self, other: RationalRate) -> RationalRate {
+        let (a, b) = (self.numerator as i128, self.denominator as i128);
+        let (c, d) = (other.numerator as i128, other.denominator as i128);
+        Self::from_i128_or_panic(a * d + c * b, b * d)
+    }
+}
+
+impl Sub for RationalRate {
+    type Output = RationalRate;
+
+    fn sub(self, other: RationalRate) -> RationalRate {
+        let (a, b) = (self.numerator as i128, self.denominator as i128);
+        let (c, d) = (other.numerator as i128, other.denominator as i128);
+        Self::from_i128_or_panic(a * d - c * b, b * d)
+    }
+}
+
+impl Mul for RationalRate {
+    type Output = RationalRate;
+
+    fn mul(self, other: RationalRate) -> RationalRate {
+        let (a, b) = (self.numerator as i128, self.denominator as i128);
+        let (c, d) = (other.numerator as i128, other.denominator as i128);
+        Self::from_i128_or_panic(a * c, b * d)
+    }
+}
+
+impl Ord for RationalRate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Both denominators are positive, so cross-multiplying keeps the order.
+        let left = self.numerator as i128 * other.denominator as i128;
+        let right = other.numerator as i128 * self.denominator as i128;
+        left.cmp(&right)
+    }
+}
+
+impl PartialOrd for RationalRate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 pub(crate) fn is_discrete_data_format(format: u32) -> bool {
     // Check is based on mxlIsDiscreteDataFormat, which is inline, thus not accessible in mxl_sys.
     format == mxl_sys::MXL_DATA_FORMAT_VIDEO || format == mxl_sys::MXL_DATA_FORMAT_DATA
@@ -39,6 +360,15 @@ pub struct FlowInfo {
     pub runtime: FlowRuntimeInfo,
 }
 
+impl std::fmt::Debug for FlowInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FlowInfo")
+            .field("config", &self.config)
+            .field("runtime", &self.runtime)
+            .finish()
+    }
+}
+
 pub struct FlowConfigInfo {
     pub(crate) value: mxl_sys::FlowConfigInfo,
 }
@@ -68,13 +398,101 @@ impl FlowConfigInfo {
         CommonFlowConfigInfo(&self.value.common)
     }
 
+    /// Typed access to the configuration of a discrete (video or data) flow.
+    pub fn discrete_info(&self) -> Result<DiscreteFlowInfo<'_>> {
+        self.discrete()?;
+        Ok(DiscreteFlowInfo {
+            config: &self.value,
+            video: None,
+        })
+    }
+
+    /// Same as `discrete_info`, also giving access to the frame dimensions of a video flow, which
+    /// are taken from `flow_def`, the definition of the flow (see `MxlInstance::get_flow_def`).
+    pub fn discrete_info_with_def<'a>(
+        &'a self,
+        flow_def: &'a FlowDef,
+    ) -> Result<DiscreteFlowInfo<'a>> {
+        self.discrete()?;
+        if flow_def.id != self.common().id() {
+            return Err(Error::Other(format!(
+                "Flow definition of {} does not match flow {}.",
+                flow_def.id,
+                self.common().id()
+            )));
+        }
+        let video = match &flow_def.details {
+            FlowDefDetails::Video(video) => Some(video),
+            _ => None,
+        };
+        Ok(DiscreteFlowInfo {
+            config: &self.value,
+            video,
+        })
+    }
+
     pub fn is_discrete_flow(&self) -> bool {
         is_discrete_data_format(self.value.common.format)
     }
+
+    /// How many grains or samples back from the head of the flow can be read, taken from the flow
+    /// configuration. For discrete flows, this is the grain count of the ring buffer. For
+    /// continuous flows, MXL only lets readers access half of the sample ring buffer, the other
+    /// half being reserved for the writer, so this is half the buffer length. Returns `None` for an
+    /// empty ring buffer.
+    pub fn buffer_depth(&self) -> Option<u64> {
+        let depth = match self.discrete() {
+            Ok(discrete) => u64::from(discrete.grainCount),
+            Err(_) => u64::from(self.continuous().ok()?.bufferLength) / 2,
+        };
+        (depth > 0).then_some(depth)
+    }
+
+    /// Whether every grain of the flow can be decoded on its own, i.e. is a sync point a reader
+    /// can seek to. Neither `mxlGrainInfo` nor the flow configuration carries a sync point flag,
+    /// so this relies on the formats: MXL only carries uncompressed video (v210) and SMPTE 291
+    /// data, whose grains are all independent. Continuous flows have no grains, so this is false
+    /// for them.
+    pub fn grains_are_independent(&self) -> bool {
+        self.is_discrete_flow()
+    }
+}
+
+/// Prints the meaningful fields only, and for the union only the variant matching the format.
+impl std::fmt::Debug for FlowConfigInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let common = self.common();
+        let rate = common.grain_or_sample_rate();
+        let mut debug = f.debug_struct("FlowConfigInfo");
+        debug
+            .field("id", &common.id())
+            .field("format", &common.data_format())
+            .field(
+                "rate",
+                &format_args!("{}/{}", rate.numerator, rate.denominator),
+            )
+            .field("payload_location", &common.payload_location());
+        if let Ok(discrete) = self.discrete_info() {
+            debug
+                .field("grain_count", &discrete.grain_count())
+                .field("slice_sizes", &discrete.slice_sizes());
+        } else if let Ok(continuous) = self.continuous() {
+            debug
+                .field("channel_count", &continuous.channelCount)
+                .field("buffer_length", &continuous.bufferLength);
+        }
+        debug.finish()
+    }
 }
 
 pub struct CommonFlowConfigInfo<'a>(&'a mxl_sys::CommonFlowConfigInfo);
 
+impl<'a> From<&'a mxl_sys::CommonFlowConfigInfo> for CommonFlowConfigInfo<'a> {
+    fn from(value: &'a mxl_sys::CommonFlowConfigInfo) -> Self {
+        Self(value)
+    }
+}
+
 impl CommonFlowConfigInfo<'_> {
     pub fn id(&self) -> Uuid {
         Uuid::from_bytes(self.0.id)
@@ -122,13 +540,148 @@ impl CommonFlowConfigInfo<'_> {
         self.0.maxSyncBatchSizeHint
     }
 
-    pub fn payload_location(&self) -> u32 {
-        self.0.payloadLocation
+    /// Batch size, in samples or slices, in which to read the flow. The first available of the
+    /// following is used:
+    /// 1. the sync batch size hint, as readers are woken up at this granularity;
+    /// 2. the commit batch size hint, as data is written at this granularity;
+    /// 3. the number of samples or grains in 10 ms, at least 1.
+    pub fn recommended_read_batch(&self) -> usize {
+        if self.0.maxSyncBatchSizeHint != 0 {
+            self.0.maxSyncBatchSizeHint as usize
+        } else if self.0.maxCommitBatchSizeHint != 0 {
+            self.0.maxCommitBatchSizeHint as usize
+        } else {
+            self.0
+                .grainRate
+                .samples_in(Duration::from_millis(10))
+                .map_or(1, |count| count.max(1) as usize)
+        }
+    }
+
+    /// Batch size, in samples or slices, in which to write the flow: the commit batch size hint,
+    /// so that writers and readers (see `recommended_read_batch`) agree on the granularity.
+    /// `None` means that the flow has no hint and the writer should use its own default.
+    pub fn recommended_write_batch(&self) -> Option<u64> {
+        match self.0.maxCommitBatchSizeHint {
+            0 => None,
+            hint => Some(hint as u64),
+        }
+    }
+
+    pub fn payload_location(&self) -> PayloadLocation {
+        PayloadLocation::from(self.0.payloadLocation)
     }
 
     pub fn device_index(&self) -> i32 {
         self.0.deviceIndex
     }
+
+    /// The payload location together with the device index, which is only present for payloads
+    /// in device memory.
+    pub fn payload_placement(&self) -> (PayloadLocation, Option<DeviceIndex>) {
+        let location = self.payload_location();
+        let device_index = match location {
+            PayloadLocation::Device => DeviceIndex::try_from(self.0.deviceIndex).ok(),
+            _ => None,
+        };
+        (location, device_index)
+    }
+}
+
+/// Configuration of a discrete flow. Please note that the frame dimensions are not part of the
+/// MXL flow configuration; they are only available in the flow definition (see
+/// `MxlInstance::get_flow_def`), so `width`, `height` and `grain_size` of video flows require the
+/// info to be obtained with `FlowConfigInfo::discrete_info_with_def`. The number of lines of a
+/// video frame is also the `total_slices` of its grains.
+pub struct DiscreteFlowInfo<'a> {
+    config: &'a mxl_sys::FlowConfigInfo,
+    video: Option<&'a FlowDefVideo>,
+}
+
+impl DiscreteFlowInfo<'_> {
+    fn discrete(&self) -> &mxl_sys::DiscreteFlowConfigInfo {
+        // The constructors check that the flow is discrete.
+        unsafe { &self.config.__bindgen_anon_1.discrete }
+    }
+
+    fn video_def(&self) -> Result<&FlowDefVideo> {
+        self.check_video()?;
+        self.video.ok_or_else(|| {
+            Error::Other(
+                "The frame dimensions are not part of the flow configuration, see \
+                 `FlowConfigInfo::discrete_info_with_def`."
+                    .to_string(),
+            )
+        })
+    }
+
+    fn check_video(&self) -> Result<()> {
+        let data_format = DataFormat::from(self.config.common.format);
+        if data_format != DataFormat::Video {
+            return Err(Error::Other(format!(
+                "Flow format is {:?}, video required.",
+                data_format
+            )));
+        }
+        Ok(())
+    }
+
+    /// Size in bytes of a slice of each of the planes of a grain. For video, a slice is a line
+    /// including any padding, for data it is a single byte.
+    pub fn slice_sizes(&self) -> &[u32] {
+        let slice_sizes = &self.discrete().sliceSizes;
+        let planes = slice_sizes
+            .iter()
+            .position(|size| *size == 0)
+            .unwrap_or(slice_sizes.len());
+        &slice_sizes[..planes]
+    }
+
+    /// Number of grains in the ring buffer.
+    pub fn grain_count(&self) -> u32 {
+        self.discrete().grainCount
+    }
+
+    /// Size in bytes of a line of the first plane of a video frame, including any padding.
+    pub fn stride(&self) -> Result<usize> {
+        self.check_video()?;
+        Ok(self.discrete().sliceSizes[0] as usize)
+    }
+
+    pub fn frame_rate(&self) -> Result<mxl_sys::Rational> {
+        self.check_video()?;
+        Ok(self.config.common.grainRate)
+    }
+
+    /// Width in pixels of a video frame.
+    pub fn width(&self) -> Result<u32> {
+        Ok(self.video_def()?.frame_width as u32)
+    }
+
+    /// Height in lines of a video frame.
+    pub fn height(&self) -> Result<u32> {
+        Ok(self.video_def()?.frame_height as u32)
+    }
+
+    /// Size in bytes of the payload of a grain, i.e. the sum of the `slice_sizes` times the number
+    /// of slices of a grain. A video grain holds a whole frame, or a single field for interlaced
+    /// video. A data grain always holds `MXL_DATA_FORMAT_GRAIN_SIZE` bytes.
+    pub fn grain_size(&self) -> Result<usize> {
+        let slices = match DataFormat::from(self.config.common.format) {
+            DataFormat::Video => {
+                let video = self.video_def()?;
+                match video.interlace_mode {
+                    InterlaceMode::Progressive => video.frame_height as usize,
+                    InterlaceMode::InterlacedTff | InterlaceMode::InterlacedBff => {
+                        video.frame_height as usize / 2
+                    }
+                }
+            }
+            _ => crate::MXL_DATA_FORMAT_GRAIN_SIZE,
+        };
+        let slice_size: usize = self.slice_sizes().iter().map(|size| *size as usize).sum();
+        Ok(slice_size * slices)
+    }
 }
 
 pub struct FlowRuntimeInfo {
@@ -147,4 +700,37 @@ impl FlowRuntimeInfo {
     pub fn last_read_time(&self) -> u64 {
         self.value.lastReadTime
     }
+
+    /// The last write time as a `SystemTime`.
+    ///
+    /// MXL timestamps are TAI, whose epoch coincides with the Unix epoch, but TAI does not have
+    /// leap seconds. The returned value is therefore ahead of the UTC wall clock by the current
+    /// TAI-UTC offset (37 seconds since 2017) and must not be displayed as local time as is.
+    pub fn last_write_instant(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_nanos(self.value.lastWriteTime)
+    }
+
+    /// How long the flow has not been written to, as of `now` (usually `MxlInstance::get_time`).
+    /// Both times are TAI, so no leap second correction is needed. Returns zero if the last write
+    /// is later than `now`.
+    pub fn staleness(&self, now: Timestamp) -> Duration {
+        now.checked_sub(Timestamp::from_nanos(self.value.lastWriteTime))
+            .unwrap_or_default()
+    }
+}
+
+impl From<mxl_sys::FlowRuntimeInfo> for FlowRuntimeInfo {
+    fn from(value: mxl_sys::FlowRuntimeInfo) -> Self {
+        Self { value }
+    }
+}
+
+impl std::fmt::Debug for FlowRuntimeInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FlowRuntimeInfo")
+            .field("head_index", &self.head_index())
+            .field("last_write_time", &self.last_write_time())
+            .field("last_read_time", &self.last_read_time())
+            .finish()
+    }
 }
@@ -5,12 +5,15 @@ use std::{collections::HashMap, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 
+use crate::Error;
+
+/// Typed representation of an MXL flow definition, as accepted by
+/// `MxlInstance::create_flow_writer`.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct FlowDef {
     pub id: uuid::Uuid,
     pub description: String,
     pub tags: HashMap<String, Vec<String>>,
-    pub format: String,
     pub label: String,
     pub parents: Vec<String>,
     pub media_type: String,
@@ -18,6 +21,128 @@ pub struct FlowDef {
     pub details: FlowDefDetails,
 }
 
+impl FlowDef {
+    pub fn from_json(json: &str) -> crate::Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|error| Error::Other(format!("Invalid flow definition: {error}")))
+    }
+
+    /// The NMOS format URN. On the wire, the `format` key is the tag of `details`.
+    pub fn format(&self) -> &str {
+        self.details.format()
+    }
+
+    /// Builds a flow definition from an NMOS IS-04 flow resource.
+    ///
+    /// The MXL flow definition is a subset of the NMOS flow resource, so most fields are taken as
+    /// is. The media types are mapped onto the ones supported by MXL:
+    /// - `video/raw` with 10-bit 4:2:2 YCbCr components becomes `video/v210`;
+    /// - `audio/L16` and `audio/L24` become `audio/float32`, as MXL stores audio as floats;
+    /// - `video/v210`, `audio/float32` and `video/smpte291` are kept.
+    ///
+    /// NMOS audio flows do not carry the channel count, which belongs to the source, so the
+    /// `channel_count` of the source must have been added to the resource.
+    pub fn from_nmos(resource: &str) -> crate::Result<Self> {
+        let mut value: serde_json::Value = serde_json::from_str(resource)
+            .map_err(|error| Error::Other(format!("Invalid NMOS flow resource: {error}")))?;
+        let object = value
+            .as_object_mut()
+            .ok_or_else(|| Error::Other("NMOS flow resource is not an object.".to_string()))?;
+        let media_type = object
+            .get("media_type")
+            .and_then(|media_type| media_type.as_str())
+            .ok_or_else(|| Error::Other("NMOS flow resource has no media type.".to_string()))?
+            .to_owned();
+        match media_type.as_str() {
+            "video/raw" => {
+                if !is_v210_compatible(object.get("components")) {
+                    return Err(Error::Other(
+                        "Only 10-bit 4:2:2 YCbCr raw video is supported.".to_string(),
+                    ));
+                }
+                object.insert("media_type".to_string(), "video/v210".into());
+            }
+            "audio/L16" | "audio/L24" => {
+                if !object.contains_key("channel_count") {
+                    return Err(Error::Other(
+                        "NMOS audio flow resource has no channel count.".to_string(),
+                    ));
+                }
+                object.insert("media_type".to_string(), "audio/float32".into());
+                object.insert("bit_depth".to_string(), 32.into());
+            }
+            "video/v210" | "audio/float32" | "video/smpte291" => {}
+            other => {
+                return Err(Error::Other(format!(
+                    "Unsupported NMOS media type \"{other}\"."
+                )));
+            }
+        }
+        Self::from_json(&value.to_string())
+    }
+
+    /// Checks the values MXL would otherwise only reject deep in the flow creation, with an opaque
+    /// status: the rate must be positive, audio flows must have at least one channel and video
+    /// frames must not be empty.
+    pub fn validate(&self) -> crate::Result<()> {
+        let invalid =
+            |message: String| Err(Error::Other(format!("Invalid flow definition: {message}")));
+        let rate = match &self.details {
+            FlowDefDetails::Video(video) => {
+                if video.frame_width <= 0 || video.frame_height <= 0 {
+                    return invalid(format!(
+                        "frame size {}x{} is empty.",
+                        video.frame_width, video.frame_height
+                    ));
+                }
+                &video.grain_rate
+            }
+            FlowDefDetails::Audio(audio) => {
+                if audio.channel_count <= 0 {
+                    return invalid(format!(
+                        "channel count {} must be positive.",
+                        audio.channel_count
+                    ));
+                }
+                &audio.sample_rate
+            }
+            FlowDefDetails::Data(data) => &data.grain_rate,
+        };
+        if rate.numerator <= 0 || rate.denominator <= 0 {
+            return invalid(format!(
+                "rate {}/{} must be positive.",
+                rate.numerator, rate.denominator
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn to_json(&self) -> crate::Result<String> {
+        serde_json::to_string(self)
+            .map_err(|error| Error::Other(format!("Failed to serialize flow definition: {error}")))
+    }
+}
+
+/// Whether the NMOS components describe 10-bit 4:2:2 YCbCr, the layout of v210.
+fn is_v210_compatible(components: Option<&serde_json::Value>) -> bool {
+    let Some(components) = components.and_then(|components| components.as_array()) else {
+        return false;
+    };
+    let component = |name: &str| {
+        components
+            .iter()
+            .find(|component| component["name"] == name)
+            .filter(|component| component["bit_depth"] == 10)
+            .and_then(|component| {
+                Some((component["width"].as_u64()?, component["height"].as_u64()?))
+            })
+    };
+    let (Some(y), Some(cb), Some(cr)) = (component("Y"), component("Cb"), component("Cr")) else {
+        return false;
+    };
+    components.len() == 3 && cb == cr && cb.0 * 2 == y.0 && cb.1 == y.1
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(tag = "format")]
 pub enum FlowDefDetails {
@@ -30,6 +155,17 @@ pub enum FlowDefDetails {
     Data(FlowDefData),
 }
 
+impl FlowDefDetails {
+    /// The NMOS format URN matching the variant.
+    pub fn format(&self) -> &'static str {
+        match self {
+            Self::Video(_) => "urn:x-nmos:format:video",
+            Self::Audio(_) => "urn:x-nmos:format:audio",
+            Self::Data(_) => "urn:x-nmos:format:data",
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct FlowDefVideo {
     pub grain_rate: Rate,
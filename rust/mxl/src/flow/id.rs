@@ -0,0 +1,91 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{ffi::CString, fmt, str::FromStr};
+
+use uuid::Uuid;
+
+use crate::{Error, Result};
+
+/// The id of a flow, a UUID.
+///
+/// The `MxlInstance` methods taking a flow id accept a `FlowId`, a `Uuid` or a string, which is
+/// parsed first, so that a malformed id fails with `Error::InvalidFlowId` before reaching MXL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FlowId(Uuid);
+
+impl FlowId {
+    pub fn new(id: Uuid) -> Self {
+        Self(id)
+    }
+
+    pub fn as_uuid(&self) -> &Uuid {
+        &self.0
+    }
+
+    /// The id in the lowercase hyphenated form used by MXL, e.g. in the flow directory names.
+    pub fn as_hyphenated_string(&self) -> String {
+        self.0.hyphenated().to_string()
+    }
+
+    pub(crate) fn to_c_string(self) -> CString {
+        // A hyphenated UUID never contains a nul byte.
+        CString::new(self.as_hyphenated_string()).expect("UUID contains no nul byte")
+    }
+}
+
+impl fmt::Display for FlowId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0.hyphenated(), f)
+    }
+}
+
+impl FromStr for FlowId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(Self(Uuid::parse_str(s)?))
+    }
+}
+
+impl From<Uuid> for FlowId {
+    fn from(id: Uuid) -> Self {
+        Self(id)
+    }
+}
+
+impl From<&Uuid> for FlowId {
+    fn from(id: &Uuid) -> Self {
+        Self(*id)
+    }
+}
+
+impl From<FlowId> for Uuid {
+    fn from(id: FlowId) -> Self {
+        id.0
+    }
+}
+
+impl TryFrom<&str> for FlowId {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl TryFrom<&String> for FlowId {
+    type Error = Error;
+
+    fn try_from(s: &String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl TryFrom<String> for FlowId {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
@@ -1,17 +1,80 @@
 // SPDX-FileCopyrightText: 2025 2025 Contributors to the Media eXchange Layer project.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
+
+use uuid::Uuid;
 
 use crate::{
-    DataFormat, Error, FlowConfigInfo, FlowRuntimeInfo, GrainReader, Result, SamplesReader,
+    DataFormat, Error, FlowConfigInfo, FlowRuntimeInfo, GrainReader, RationalExt, Result,
+    SamplesReader,
+    error::report_drop_error,
     flow::{FlowInfo, is_discrete_data_format},
     instance::InstanceContext,
 };
 
+/// Timeout used by the `*_default` read methods of the grain and samples readers until another one
+/// is set with `set_default_timeout`.
+pub(crate) const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest timeout handed to MXL, which adds it to the current time as a signed 64-bit number of
+/// nanoseconds. About a century, far enough from the overflow.
+const MAX_FFI_TIMEOUT: Duration = Duration::from_secs(100 * 365 * 24 * 3600);
+
+/// How long the blocking read methods wait for the requested grain or samples.
+///
+/// A `Duration` converts to `Immediate` if zero, to `Forever` if `Duration::MAX`, and to `For`
+/// otherwise, so that the read methods can still be given a plain `Duration`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timeout {
+    /// Do not wait: the read fails with `Error::OutOfRangeTooEarly` if the data is not there yet.
+    Immediate,
+    /// Wait at most the given duration: the read fails with `Error::Timeout` if the data is still
+    /// not there by then.
+    For(Duration),
+    /// Wait until the data is there, however long it takes.
+    Forever,
+}
+
+impl Timeout {
+    /// The time to wait for, `Duration::MAX` for `Forever`.
+    pub fn as_duration(&self) -> Duration {
+        match self {
+            Timeout::Immediate => Duration::ZERO,
+            Timeout::For(duration) => *duration,
+            Timeout::Forever => Duration::MAX,
+        }
+    }
+
+    /// The time to wait for as given to MXL, `Forever` being mapped to the longest timeout MXL can
+    /// take.
+    pub(crate) fn as_ffi_duration(&self) -> Duration {
+        self.as_duration().min(MAX_FFI_TIMEOUT)
+    }
+
+    pub(crate) fn as_ffi_nanos(&self) -> u64 {
+        self.as_ffi_duration().as_nanos() as u64
+    }
+}
+
+impl From<Duration> for Timeout {
+    fn from(duration: Duration) -> Self {
+        if duration.is_zero() {
+            Timeout::Immediate
+        } else if duration == Duration::MAX {
+            Timeout::Forever
+        } else {
+            Timeout::For(duration)
+        }
+    }
+}
+
 pub struct FlowReader {
     context: Arc<InstanceContext>,
     reader: mxl_sys::FlowReader,
+    id: Uuid,
+    /// The options the reader was created with, kept for `try_clone`.
+    options: String,
 }
 
 /// The MXL readers and writers are not thread-safe, so we do not implement `Sync` for them, but
@@ -66,21 +129,203 @@ pub(crate) fn get_runtime_info(
     Ok(runtime_info)
 }
 
+pub(crate) fn oldest_available_index(info: &FlowInfo) -> Result<u64> {
+    let depth = info
+        .config
+        .buffer_depth()
+        .ok_or_else(|| Error::Other("Failed to get the buffer depth of the flow.".to_owned()))?;
+    Ok(info.runtime.head_index().saturating_sub(depth - 1))
+}
+
+/// Shortest sleep between two checks of the flow head, so that a zero `WaitConfig::min_poll` does
+/// not turn the wait into a busy loop.
+const MIN_POLL_INTERVAL: Duration = Duration::from_micros(100);
+
+/// How long the `wait_for_index_with` methods of the readers sleep between checks of the flow
+/// head: `min_poll` first, then `backoff` times longer after every check, up to `max_poll`. Short
+/// intervals lower the latency, long ones the CPU usage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WaitConfig {
+    /// Intervals shorter than 100 microseconds, zero included, are raised to that.
+    pub min_poll: Duration,
+    pub max_poll: Duration,
+    /// Factor applied to the interval after every check, values below 1 are treated as 1.
+    pub backoff: f32,
+}
+
+impl WaitConfig {
+    /// The successive sleep intervals, never ending.
+    pub fn poll_intervals(&self) -> impl Iterator<Item = Duration> + use<> {
+        let max_poll = self.max_poll.max(MIN_POLL_INTERVAL);
+        let backoff = f64::from(self.backoff.max(1.0));
+        let min_poll = self.min_poll.clamp(MIN_POLL_INTERVAL, max_poll);
+        std::iter::successors(Some(min_poll), move |interval| {
+            let next =
+                Duration::try_from_secs_f64(interval.as_secs_f64() * backoff).unwrap_or(max_poll);
+            Some(next.min(max_poll))
+        })
+    }
+}
+
+/// Polls every millisecond at first, backing off to every 100 milliseconds.
+impl Default for WaitConfig {
+    fn default() -> Self {
+        Self {
+            min_poll: Duration::from_millis(1),
+            max_poll: Duration::from_millis(100),
+            backoff: 2.0,
+        }
+    }
+}
+
+/// Waits until the head of the flow reaches `index`, sleeping for the time needed to produce the
+/// missing grains or samples between the checks, or as set by `wait_config`.
+pub(crate) fn wait_for_index(
+    context: &Arc<InstanceContext>,
+    reader: mxl_sys::FlowReader,
+    index: u64,
+    timeout: Duration,
+    wait_config: Option<&WaitConfig>,
+) -> Result<()> {
+    let config_info = get_config_info(context, reader)?;
+    let period = config_info
+        .common()
+        .grain_or_sample_rate()
+        .period()
+        .ok_or_else(|| Error::Other("Invalid grain or sample rate.".to_owned()))?;
+    // Number of grains or samples that can still be read behind the head. For continuous flows,
+    // only half of the ring buffer is guaranteed to be readable, the other half is reserved for the
+    // writer.
+    let history_length = if config_info.is_discrete_flow() {
+        config_info.discrete()?.grainCount as u64
+    } else {
+        config_info.continuous()?.bufferLength as u64 / 2
+    };
+    let deadline = unsafe { context.api.get_time() }
+        .saturating_add(timeout.as_nanos().try_into().unwrap_or(u64::MAX));
+    let mut poll_intervals = wait_config.map(WaitConfig::poll_intervals);
+
+    loop {
+        let head_index = get_runtime_info(context, reader)?.headIndex;
+        if head_index >= index {
+            if head_index - index >= history_length {
+                return Err(Error::OutOfRangeTooLate);
+            }
+            return Ok(());
+        }
+
+        let now = unsafe { context.api.get_time() };
+        if now >= deadline {
+            return Err(Error::Timeout);
+        }
+        let wait = match poll_intervals.as_mut().and_then(Iterator::next) {
+            Some(interval) => interval,
+            None => period.saturating_mul(u32::try_from(index - head_index).unwrap_or(u32::MAX)),
+        };
+        let wait_ns = (wait.as_nanos() as u64).min(deadline - now);
+        unsafe { context.api.sleep_for_ns(wait_ns) };
+    }
+}
+
 impl FlowReader {
-    pub(crate) fn new(context: Arc<InstanceContext>, reader: mxl_sys::FlowReader) -> Self {
-        Self { context, reader }
+    pub(crate) fn new(
+        context: Arc<InstanceContext>,
+        reader: mxl_sys::FlowReader,
+        id: Uuid,
+        options: String,
+    ) -> Self {
+        Self {
+            context,
+            reader,
+            id,
+            options,
+        }
+    }
+
+    pub fn flow_id(&self) -> Uuid {
+        self.id
+    }
+
+    /// The options JSON the reader was created with, empty if none.
+    pub fn options(&self) -> &str {
+        &self.options
+    }
+
+    /// Opens another reader on the same flow, with the same options. The new reader has its own
+    /// read cursor and is released independently, which allows e.g. two processing stages to read
+    /// at different rates.
+    pub fn try_clone(&self) -> Result<FlowReader> {
+        crate::instance::create_flow_reader(&self.context, self.id.into(), &self.options)
     }
 
     pub fn get_info(&self) -> Result<FlowInfo> {
         get_flow_info(&self.context, self.reader)
     }
 
+    pub fn get_runtime_info(&self) -> Result<mxl_sys::FlowRuntimeInfo> {
+        get_runtime_info(&self.context, self.reader)
+    }
+
+    /// Index of the last grain or sample written to the flow, read from the runtime info only.
+    pub fn head_index(&self) -> Result<u64> {
+        Ok(self.get_runtime_info()?.headIndex)
+    }
+
+    /// Index of the oldest grain or sample still in the ring buffer, i.e. the head index minus the
+    /// `FlowConfigInfo::buffer_depth`, e.g. to clamp seek targets. Reading before it fails with
+    /// `Error::OutOfRangeTooLate`. The writer keeps moving it forward, so a reader seeking to it
+    /// should leave some margin.
+    pub fn oldest_available_index(&self) -> Result<u64> {
+        oldest_available_index(&self.get_info()?)
+    }
+
+    /// Waits until the head of the flow reaches `index`. Returns `Error::Timeout` if it does not
+    /// happen in time, and `Error::OutOfRangeTooLate` if `index` is already out of the ring buffer.
+    pub fn wait_for_index(&self, index: u64, timeout: Duration) -> Result<()> {
+        wait_for_index(&self.context, self.reader, index, timeout, None)
+    }
+
+    /// Same as `wait_for_index`, sleeping between the checks as set by `wait_config` instead of
+    /// for the time needed to produce the missing grains or samples.
+    pub fn wait_for_index_with(
+        &self,
+        index: u64,
+        timeout: Duration,
+        wait_config: &WaitConfig,
+    ) -> Result<()> {
+        wait_for_index(
+            &self.context,
+            self.reader,
+            index,
+            timeout,
+            Some(wait_config),
+        )
+    }
+
+    /// The format of the flow, read from the flow configuration only, which is cheaper than
+    /// `get_info`.
+    pub fn data_format(&self) -> Result<DataFormat> {
+        Ok(get_config_info(&self.context, self.reader)?
+            .common()
+            .data_format())
+    }
+
+    /// Whether the flow is discrete (video or data), i.e. can be read with `to_grain_reader`, read
+    /// from the flow configuration without consuming the reader.
+    pub fn is_discrete(&self) -> Result<bool> {
+        Ok(is_discrete_data_format(self.data_format()?.as_u32()))
+    }
+
+    /// Whether the flow is continuous (audio), i.e. can be read with `to_samples_reader`.
+    pub fn is_continuous(&self) -> Result<bool> {
+        Ok(self.data_format()? == DataFormat::Audio)
+    }
+
     pub fn to_grain_reader(mut self) -> Result<GrainReader> {
-        let flow_type = self.get_info()?.config.value.common.format;
-        if !is_discrete_data_format(flow_type) {
+        let data_format = self.data_format()?;
+        if !is_discrete_data_format(data_format.as_u32()) {
             return Err(Error::Other(format!(
-                "Cannot convert FlowReader to GrainReader for continuous flow of type \"{:?}\".",
-                DataFormat::from(flow_type)
+                "Cannot convert FlowReader to GrainReader for continuous flow of type \"{data_format:?}\".",
             )));
         }
         let result = GrainReader::new(self.context.clone(), self.reader);
@@ -89,14 +334,17 @@ impl FlowReader {
     }
 
     pub fn to_samples_reader(mut self) -> Result<SamplesReader> {
-        let flow_type = self.get_info()?.config.value.common.format;
-        if is_discrete_data_format(flow_type) {
+        // The configuration is needed anyway for the buffer length, so we do not go through
+        // `data_format`.
+        let config = get_config_info(&self.context, self.reader)?;
+        if config.is_discrete_flow() {
             return Err(Error::Other(format!(
                 "Cannot convert FlowReader to SamplesReader for discrete flow of type \"{:?}\".",
-                DataFormat::from(flow_type)
+                config.common().data_format()
             )));
         }
-        let result = SamplesReader::new(self.context.clone(), self.reader);
+        let buffer_length = config.continuous()?.bufferLength as usize;
+        let result = SamplesReader::new(self.context.clone(), self.reader, buffer_length);
         self.reader = std::ptr::null_mut();
         Ok(result)
     }
@@ -112,6 +360,7 @@ impl Drop for FlowReader {
             })
         {
             tracing::error!("Failed to release MXL flow reader: {:?}", err);
+            report_drop_error(&err);
         }
     }
 }
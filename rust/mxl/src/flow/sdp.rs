@@ -0,0 +1,86 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{DataFormat, Error, FlowConfigInfo, Result};
+
+/// RTP payload type used for video media lines.
+const VIDEO_PAYLOAD_TYPE: u8 = 96;
+/// RTP payload type used for audio media lines.
+const AUDIO_PAYLOAD_TYPE: u8 = 97;
+/// RTP clock rate of ST 2110-20 video.
+const VIDEO_CLOCK_RATE: u32 = 90000;
+
+impl FlowConfigInfo {
+    /// Produces a minimal ST 2110-style SDP describing the flow.
+    ///
+    /// For audio, ST 2110-30 only defines linear PCM, so the media line describes the L24 stream
+    /// a sender produces from the MXL float samples, with the sample rate and channel count of the
+    /// flow. For video, only the frame rate is part of the flow configuration; the frame
+    /// dimensions, sampling and colorimetry parameters required by ST 2110-20 have to be appended
+    /// to the `fmtp` line from the flow definition. Data and unspecified flows are not supported.
+    pub fn to_sdp(&self) -> Result<String> {
+        let common = self.common();
+        let rate = common.grain_or_sample_rate();
+        if rate.numerator <= 0 || rate.denominator <= 0 {
+            return Err(Error::Other(format!(
+                "Invalid grain or sample rate {}/{}.",
+                rate.numerator, rate.denominator
+            )));
+        }
+
+        let mut sdp = String::new();
+        push_line(&mut sdp, "v=0");
+        push_line(&mut sdp, "o=- 0 0 IN IP4 0.0.0.0");
+        push_line(&mut sdp, &format!("s={}", common.id()));
+        push_line(&mut sdp, "t=0 0");
+        match common.data_format() {
+            DataFormat::Video => {
+                let frame_rate = if rate.denominator == 1 {
+                    rate.numerator.to_string()
+                } else {
+                    format!("{}/{}", rate.numerator, rate.denominator)
+                };
+                push_line(&mut sdp, &format!("m=video 0 RTP/AVP {VIDEO_PAYLOAD_TYPE}"));
+                push_line(
+                    &mut sdp,
+                    &format!("a=rtpmap:{VIDEO_PAYLOAD_TYPE} raw/{VIDEO_CLOCK_RATE}"),
+                );
+                push_line(
+                    &mut sdp,
+                    &format!("a=fmtp:{VIDEO_PAYLOAD_TYPE} exactframerate={frame_rate}"),
+                );
+            }
+            DataFormat::Audio => {
+                if rate.denominator != 1 {
+                    return Err(Error::Other(format!(
+                        "Sample rate {}/{} is not an integer.",
+                        rate.numerator, rate.denominator
+                    )));
+                }
+                let channels = self.continuous()?.channelCount;
+                push_line(&mut sdp, &format!("m=audio 0 RTP/AVP {AUDIO_PAYLOAD_TYPE}"));
+                push_line(
+                    &mut sdp,
+                    &format!(
+                        "a=rtpmap:{AUDIO_PAYLOAD_TYPE} L24/{}/{channels}",
+                        rate.numerator
+                    ),
+                );
+                push_line(&mut sdp, "a=ptime:1");
+            }
+            data_format => {
+                return Err(Error::Other(format!(
+                    "Flow format is {data_format}, SDP is only available for video and audio."
+                )));
+            }
+        }
+        push_line(&mut sdp, "a=ts-refclk:ptp=traceable");
+        push_line(&mut sdp, "a=mediaclk:direct=0");
+        Ok(sdp)
+    }
+}
+
+fn push_line(sdp: &mut String, line: &str) {
+    sdp.push_str(line);
+    sdp.push_str("\r\n");
+}
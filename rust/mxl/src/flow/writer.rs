@@ -4,17 +4,90 @@
 use std::sync::Arc;
 
 use crate::{
-    DataFormat, Error, GrainWriter, Result, SamplesWriter,
+    DataFormat, Error, FlowConfigInfo, GrainWriter, Result, SamplesWriter,
+    error::report_drop_error,
     flow::is_discrete_data_format,
     instance::{InstanceContext, create_flow_reader},
 };
 
+/// Result of `MxlInstance::create_flow_writer`.
+pub struct CreatedFlow {
+    pub writer: FlowWriter,
+    pub config: FlowConfigInfo,
+    /// `true` if the flow was newly created, `false` if a flow with the same id already existed
+    /// and the writer was attached to it.
+    pub was_created: bool,
+}
+
+/// Typed options of `MxlInstance::create_flow_writer_opts`, serialized into the options JSON MXL
+/// expects.
+///
+/// MXL currently only supports the batch size hints as writer options. The payload location and
+/// device index of a flow are not writer options, they come from the flow definition.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WriterOptions {
+    max_commit_batch_size_hint: Option<u32>,
+    max_sync_batch_size_hint: Option<u32>,
+}
+
+impl WriterOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The maximum number of grain slices or samples the writer commits at once, see
+    /// `CommonFlowConfigInfo::max_commit_batch_size_hint`. Must be at least 1.
+    pub fn max_commit_batch_size_hint(mut self, hint: u32) -> Self {
+        self.max_commit_batch_size_hint = Some(hint);
+        self
+    }
+
+    /// The maximum number of grain slices or samples after which readers are notified, see
+    /// `CommonFlowConfigInfo::max_sync_batch_size_hint`. Must be a multiple of the commit batch
+    /// size hint.
+    pub fn max_sync_batch_size_hint(mut self, hint: u32) -> Self {
+        self.max_sync_batch_size_hint = Some(hint);
+        self
+    }
+
+    /// Serializes the options into the JSON object MXL expects, after checking them, because MXL
+    /// rejects invalid options with an opaque status only.
+    pub fn as_option_string(&self) -> Result<String> {
+        let invalid =
+            |message: &str| Err(Error::Other(format!("Invalid writer options: {message}")));
+        if self.max_commit_batch_size_hint == Some(0) {
+            return invalid("the commit batch size hint must be at least 1.");
+        }
+        if let Some(sync_hint) = self.max_sync_batch_size_hint {
+            if sync_hint == 0 {
+                return invalid("the sync batch size hint must be at least 1.");
+            }
+            if sync_hint % self.max_commit_batch_size_hint.unwrap_or(1) != 0 {
+                return invalid(
+                    "the sync batch size hint must be a multiple of the commit batch size hint.",
+                );
+            }
+        }
+
+        let mut options = serde_json::Map::new();
+        if let Some(hint) = self.max_commit_batch_size_hint {
+            options.insert("maxCommitBatchSizeHint".to_owned(), hint.into());
+        }
+        if let Some(hint) = self.max_sync_batch_size_hint {
+            options.insert("maxSyncBatchSizeHint".to_owned(), hint.into());
+        }
+        Ok(serde_json::Value::Object(options).to_string())
+    }
+}
+
 /// Generic MXL Flow Writer, which can be further used to build either the "discrete" (grain-based
 /// data like video frames or meta) or "continuous" (audio samples) flow writers in MXL terminology.
 pub struct FlowWriter {
     context: Arc<InstanceContext>,
     writer: mxl_sys::FlowWriter,
     id: uuid::Uuid,
+    /// The config info returned when the writer was created.
+    config: mxl_sys::FlowConfigInfo,
 }
 
 /// The MXL readers and writers are not thread-safe, so we do not implement `Sync` for them, but
@@ -26,57 +99,84 @@ impl FlowWriter {
         context: Arc<InstanceContext>,
         writer: mxl_sys::FlowWriter,
         id: uuid::Uuid,
+        config: mxl_sys::FlowConfigInfo,
     ) -> Self {
         Self {
             context,
             writer,
             id,
+            config,
         }
     }
 
     pub fn to_grain_writer(mut self) -> Result<GrainWriter> {
-        let flow_type = self.get_flow_type()?;
+        let flow_type = self.get_config_info()?.value.common.format;
         if !is_discrete_data_format(flow_type) {
             return Err(Error::Other(format!(
                 "Cannot convert FlowWriter to GrainWriter for continuous flow of type \"{:?}\".",
                 DataFormat::from(flow_type)
             )));
         }
-        let result = GrainWriter::new(self.context.clone(), self.writer);
+        let result = GrainWriter::new(self.context.clone(), self.writer, self.id);
         self.writer = std::ptr::null_mut();
         Ok(result)
     }
 
     pub fn to_samples_writer(mut self) -> Result<SamplesWriter> {
-        let flow_type = self.get_flow_type()?;
-        if is_discrete_data_format(flow_type) {
+        // The configuration is needed anyway for the buffer length, as in
+        // `FlowReader::to_samples_reader`.
+        let config = self.get_config_info()?;
+        if config.is_discrete_flow() {
             return Err(Error::Other(format!(
                 "Cannot convert FlowWriter to SamplesWriter for discrete flow of type \"{:?}\".",
-                DataFormat::from(flow_type)
+                config.common().data_format()
             )));
         }
-        let result = SamplesWriter::new(self.context.clone(), self.writer);
+        let buffer_length = config.continuous()?.bufferLength as usize;
+        let result = SamplesWriter::new(self.context.clone(), self.writer, self.id, buffer_length);
         self.writer = std::ptr::null_mut();
         Ok(result)
     }
 
-    fn get_flow_type(&self) -> Result<u32> {
-        // This feels pretty ugly, but currently, the only way how to get a flow type in MXL is to
-        // use a reader.
-        let reader = create_flow_reader(&self.context, &self.id.to_string()).map_err(|error| {
+    pub fn id(&self) -> uuid::Uuid {
+        self.id
+    }
+
+    fn get_config_info(&self) -> Result<FlowConfigInfo> {
+        // The config is normally known from when the writer was created.
+        if DataFormat::from(self.config.common.format) != DataFormat::Unspecified {
+            return Ok(FlowConfigInfo { value: self.config });
+        }
+        // Fall back to asking a temporary reader, which is currently the only other way how to
+        // get a flow config in MXL.
+        let reader = create_flow_reader(&self.context, self.id.into(), "").map_err(|error| {
             Error::Other(format!(
-                "Error while creating flow reader to get the flow type: {error}"
+                "Error while creating flow reader to get the flow config: {error}"
             ))
         })?;
         let flow_info = reader.get_info().map_err(|error| {
             Error::Other(format!(
-                "Error while getting flow type from temporary reader: {error}"
+                "Error while getting flow config from temporary reader: {error}"
             ))
         })?;
-        Ok(flow_info.config.value.common.format)
+        Ok(flow_info.config)
     }
 }
 
+/// Runtime info of the flow written by a grain or samples writer. MXL has no way to query it
+/// through the writer handle, so a temporary reader is used.
+pub(crate) fn get_writer_runtime_info(
+    context: &Arc<InstanceContext>,
+    id: uuid::Uuid,
+) -> Result<mxl_sys::FlowRuntimeInfo> {
+    let reader = create_flow_reader(context, id.into(), "").map_err(|error| {
+        Error::Other(format!(
+            "Error while creating flow reader to get the runtime info: {error}"
+        ))
+    })?;
+    reader.get_runtime_info()
+}
+
 impl Drop for FlowWriter {
     fn drop(&mut self) {
         if !self.writer.is_null()
@@ -87,6 +187,7 @@ impl Drop for FlowWriter {
             })
         {
             tracing::error!("Failed to release MXL flow writer: {:?}", err);
+            report_drop_error(&err);
         }
     }
 }
@@ -1,7 +1,14 @@
 // SPDX-FileCopyrightText: 2025 2025 Contributors to the Media eXchange Layer project.
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "async")]
+pub mod async_reader;
 pub mod data;
+mod dump;
+pub mod iter;
+pub mod prefetch;
 pub mod reader;
+pub mod shared_writer;
+pub mod traits;
 pub mod write_access;
 pub mod writer;
@@ -0,0 +1,120 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use futures::Stream;
+
+use crate::{Error, GrainReader, OwnedGrainData, Result};
+
+/// Async wrapper over [`GrainReader`], reading grains sequentially starting at the flow head.
+///
+/// `GrainReader` is `Send` but not `Sync`, so it is kept behind a mutex and each read is executed
+/// on the tokio blocking pool. This allows many flows to be serviced from a single runtime without
+/// dedicating a thread to each of them.
+///
+/// The futures returned by `next_grain` are cancel-safe. Dropping a future before it completes
+/// lets the blocking read finish in the background and does not advance the read index, so the
+/// next call will read the same grain again.
+pub struct AsyncGrainReader {
+    reader: Arc<Mutex<GrainReader>>,
+    next_index: AtomicU64,
+    timeout: Duration,
+}
+
+impl AsyncGrainReader {
+    /// Creates the wrapper. The first grain returned by `next_grain` is the current head of the
+    /// flow. `timeout` applies to each individual grain read.
+    pub fn new(reader: GrainReader, timeout: Duration) -> Result<Self> {
+        let head_index = reader.get_runtime_info()?.headIndex;
+        Ok(Self {
+            reader: Arc::new(Mutex::new(reader)),
+            next_index: AtomicU64::new(head_index),
+            timeout,
+        })
+    }
+
+    /// Index of the grain that the next call to `next_grain` will read.
+    pub fn next_index(&self) -> u64 {
+        self.next_index.load(Ordering::Acquire)
+    }
+
+    /// Waits for the next complete grain and returns a copy of it.
+    ///
+    /// If the reader fell behind and the grain was already overwritten, `Error::OutOfRangeTooLate`
+    /// is returned and the read index is resynchronized to the current head of the flow.
+    pub async fn next_grain(&self) -> Result<OwnedGrainData> {
+        let index = self.next_index();
+        let reader = self.reader.clone();
+        let timeout = self.timeout;
+        let outcome = tokio::task::spawn_blocking(move || {
+            let reader = reader
+                .lock()
+                .map_err(|_| Error::Other("Grain reader mutex poisoned.".to_owned()))?;
+            match reader.get_complete_grain(index, timeout) {
+                Ok(grain) => Ok(Ok(grain.to_owned())),
+                Err(Error::OutOfRangeTooLate) => Ok(Err(reader.get_runtime_info()?.headIndex)),
+                Err(err) => Err(err),
+            }
+        })
+        .await
+        .map_err(|err| Error::Other(format!("Blocking grain read failed: {err}")))??;
+
+        // The index is only updated once the read completed, so a cancelled future leaves the
+        // reader state untouched.
+        match outcome {
+            Ok(grain) => {
+                self.next_index.store(index + 1, Ordering::Release);
+                Ok(grain)
+            }
+            Err(head_index) => {
+                self.next_index.store(head_index, Ordering::Release);
+                Err(Error::OutOfRangeTooLate)
+            }
+        }
+    }
+}
+
+impl GrainReader {
+    /// Turns the reader into a stream of grains, starting at `start_index`.
+    ///
+    /// Grains are read sequentially, and the next grain is only read when the stream is polled, so
+    /// a slow consumer naturally applies backpressure. If the reader falls behind,
+    /// `Error::OutOfRangeTooLate` is yielded and reading resumes from the current head of the flow.
+    /// On `Error::Timeout`, the same grain is read again on the next poll. Any other error is
+    /// yielded and ends the stream, as reading again would fail the same way. The stream also ends
+    /// when the blocking read task can no longer hand the reader back, e.g. because the runtime is
+    /// shutting down.
+    pub fn into_stream(
+        self,
+        start_index: u64,
+        timeout: Duration,
+    ) -> impl Stream<Item = Result<OwnedGrainData>> {
+        futures::stream::unfold(Some((self, start_index)), move |state| async move {
+            let (reader, index) = state?;
+            let (reader, result, next_index) = tokio::task::spawn_blocking(move || {
+                let (result, next_index) = match reader.get_complete_grain(index, timeout) {
+                    Ok(grain) => (Ok(grain.to_owned()), Some(index + 1)),
+                    Err(Error::OutOfRangeTooLate) => {
+                        let head_index = reader
+                            .get_runtime_info()
+                            .map_or(index, |info| info.headIndex);
+                        (Err(Error::OutOfRangeTooLate), Some(head_index))
+                    }
+                    Err(Error::Timeout) => (Err(Error::Timeout), Some(index)),
+                    Err(err) => (Err(err), None),
+                };
+                (reader, result, next_index)
+            })
+            .await
+            .ok()?;
+            Some((result, next_index.map(|next_index| (reader, next_index))))
+        })
+    }
+}
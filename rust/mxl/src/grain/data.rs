@@ -1,6 +1,59 @@
 // SPDX-FileCopyrightText: 2025 2025 Contributors to the Media eXchange Layer project.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::ops::BitOr;
+
+use crate::{Error, Result};
+
+/// Typed view of the `flags` of `mxlGrainInfo`. Bits without a constant here are preserved, so
+/// that flags introduced by a newer MXL library are not lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct GrainFlags(u32);
+
+impl GrainFlags {
+    /// The writer marked the grain as invalid, e.g. because its source dropped the frame. The
+    /// payload must not be used.
+    pub const INVALID: GrainFlags = GrainFlags(mxl_sys::MXL_GRAIN_FLAG_INVALID);
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    pub const fn bits(&self) -> u32 {
+        self.0
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(&self, other: GrainFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub const fn is_invalid(&self) -> bool {
+        self.contains(Self::INVALID)
+    }
+}
+
+impl From<u32> for GrainFlags {
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+impl BitOr for GrainFlags {
+    type Output = GrainFlags;
+
+    fn bitor(self, other: GrainFlags) -> GrainFlags {
+        Self(self.0 | other.0)
+    }
+}
+
 pub struct GrainData<'a> {
     /// The grain payload. This may be a partial payload if the grain is not complete.
     /// The length of this slice is given by `commitedSize` in `mxlGrainInfo`.
@@ -9,10 +62,35 @@ pub struct GrainData<'a> {
     /// The total size of the grain payload, which may be larger than `payload.len()` if the grain is partial.
     pub total_size: usize,
 
+    /// Grain flags, e.g. `MXL_GRAIN_FLAG_INVALID`. This is the only per-grain metadata carried by
+    /// the MXL grain header, which has no user data area.
     pub flags: u32,
 }
 
 impl<'a> GrainData<'a> {
+    /// Iterates over the first `height` rows of a video frame, each `stride` bytes long including
+    /// any padding, e.g. `grain.rows(info.stride()?, frame_height)` with `DiscreteFlowInfo::stride`
+    /// and the frame height of the flow definition. Fails with `Error::InvalidArg` if `stride` is
+    /// zero or the rows do not fit into the payload.
+    pub fn rows(&self, stride: usize, height: usize) -> Result<impl Iterator<Item = &'a [u8]>> {
+        let size = stride.checked_mul(height).ok_or(Error::InvalidArg)?;
+        if stride == 0 || size > self.payload.len() {
+            return Err(Error::InvalidArg);
+        }
+        Ok(self.payload[..size].chunks_exact(stride))
+    }
+
+    /// CRC-32 (IEEE 802.3) of the payload, to check its integrity, see
+    /// `GrainReader::get_complete_grain_verified`.
+    pub fn crc32(&self) -> u32 {
+        crc32(self.payload)
+    }
+
+    /// Typed view of `flags`.
+    pub fn flags(&self) -> GrainFlags {
+        self.flags.into()
+    }
+
     pub fn to_owned(&self) -> OwnedGrainData {
         self.into()
     }
@@ -26,14 +104,67 @@ impl<'a> AsRef<GrainData<'a>> for GrainData<'a> {
 
 pub struct OwnedGrainData {
     pub payload: Vec<u8>,
+
+    /// Grain flags, see `GrainData::flags`.
+    pub flags: u32,
+}
+
+impl OwnedGrainData {
+    /// See `GrainData::crc32`.
+    pub fn crc32(&self) -> u32 {
+        crc32(&self.payload)
+    }
+
+    /// Typed view of `flags`.
+    pub fn flags(&self) -> GrainFlags {
+        self.flags.into()
+    }
 }
 
 impl<'a> From<&GrainData<'a>> for OwnedGrainData {
     fn from(value: &GrainData<'a>) -> Self {
         Self {
             payload: value.payload.to_vec(),
+            flags: value.flags,
+        }
+    }
+}
+
+/// Bitwise CRC-32 with the reflected IEEE 802.3 polynomial. It is slow compared to table driven
+/// implementations, but it is meant for debugging only.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
         }
     }
+    !crc
+}
+
+/// State of a grain as seen by a reader, see `GrainReader::get_grain_status`.
+pub enum GrainStatus {
+    /// All the slices of the grain are committed.
+    Complete(OwnedGrainData),
+    /// The writer committed only the first `committed` of the `total` slices so far.
+    Partial { committed: u16, total: u16 },
+    /// The writer did not reach the grain yet.
+    NotReady,
+}
+
+/// How many slices of a grain are committed, see `GrainReader::get_grain_with_policy`. Only the
+/// first `committed` slices of the payload are meaningful.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrainSlices {
+    pub committed: u16,
+    pub total: u16,
+}
+
+impl GrainSlices {
+    pub fn is_complete(&self) -> bool {
+        self.committed == self.total
+    }
 }
 
 impl<'a> From<GrainData<'a>> for OwnedGrainData {
@@ -0,0 +1,55 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{path::Path, time::Duration};
+
+use crate::{
+    Error, GrainReader, Result,
+    flowdef::{FlowDef, FlowDefDetails},
+    instance::get_flow_def,
+};
+
+impl GrainReader {
+    /// Writes the payload of the grain at `index` as is to `path`, and a description of its
+    /// layout to a sidecar file named after `path` with an additional `.json` extension.
+    ///
+    /// The sidecar holds the geometry of the discrete flow configuration (see
+    /// `DiscreteFlowInfo`) and, for video, the frame dimensions from the flow definition. This
+    /// helps checking whether a producer lays out its pixels correctly.
+    pub fn dump_frame(&self, index: u64, path: impl AsRef<Path>, timeout: Duration) -> Result<()> {
+        let path = path.as_ref();
+        let config = self.get_config_info()?;
+        let discrete_info = config.discrete_info()?;
+        let flow_id = config.common().id();
+        let flow_def = FlowDef::from_json(&get_flow_def(&self.context, flow_id.into())?)?;
+        let (frame_width, frame_height) = match &flow_def.details {
+            FlowDefDetails::Video(video) => (Some(video.frame_width), Some(video.frame_height)),
+            _ => (None, None),
+        };
+        let grain = self.get_complete_grain(index, timeout)?;
+
+        let sidecar = serde_json::json!({
+            "flow_id": flow_id,
+            "index": index,
+            "format": config.common().data_format().to_string(),
+            "media_type": flow_def.media_type,
+            "frame_width": frame_width,
+            "frame_height": frame_height,
+            "grain_size": grain.total_size,
+            "slice_sizes": discrete_info.slice_sizes(),
+        });
+        let mut sidecar_path = path.as_os_str().to_owned();
+        sidecar_path.push(".json");
+
+        let io_error = |path: &Path, error: std::io::Error| {
+            Error::Other(format!(
+                "Failed to write frame dump \"{}\": {error}",
+                path.display()
+            ))
+        };
+        std::fs::write(path, grain.payload).map_err(|error| io_error(path, error))?;
+        let sidecar_path = Path::new(&sidecar_path);
+        std::fs::write(sidecar_path, sidecar.to_string())
+            .map_err(|error| io_error(sidecar_path, error))
+    }
+}
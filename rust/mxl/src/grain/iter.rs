@@ -0,0 +1,47 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{ops::Range, time::Duration};
+
+use crate::{GrainReader, OwnedGrainData, Result};
+
+/// Iterator over a contiguous range of grains, created by `GrainReader::grains`.
+///
+/// Every call to `next` reads one complete grain, waiting for it for at most the given timeout.
+/// The first error is yielded once, after which the iterator is exhausted.
+pub struct GrainIter<'a> {
+    reader: &'a GrainReader,
+    range: Range<u64>,
+    timeout: Duration,
+}
+
+impl<'a> GrainIter<'a> {
+    pub(crate) fn new(reader: &'a GrainReader, range: Range<u64>, timeout: Duration) -> Self {
+        Self {
+            reader,
+            range,
+            timeout,
+        }
+    }
+}
+
+impl Iterator for GrainIter<'_> {
+    type Item = Result<OwnedGrainData>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.range.next()?;
+        let result = self
+            .reader
+            .get_complete_grain(index, self.timeout)
+            .map(|grain| grain.to_owned());
+        if result.is_err() {
+            // Stop after the first error.
+            self.range = self.range.end..self.range.end;
+        }
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.range.size_hint().1)
+    }
+}
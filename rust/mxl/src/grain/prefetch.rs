@@ -0,0 +1,100 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver},
+    },
+    thread::JoinHandle,
+};
+
+use crate::{Error, GrainReader, OwnedGrainData, Result};
+
+/// Reads the grains of a flow in sequence, fetching up to `depth` grains ahead of the consumer on
+/// a background thread, e.g. for a playback tool that cannot afford to wait for every grain.
+///
+/// Each grain is waited for with the default timeout of the reader, see
+/// `GrainReader::set_default_timeout`. The first error stops the prefetching, it is returned by
+/// `next` once the grains fetched before it have been consumed.
+pub struct PrefetchingGrainReader {
+    grains: Option<Receiver<Result<OwnedGrainData>>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+    next_index: u64,
+}
+
+impl PrefetchingGrainReader {
+    /// Starts prefetching with the first grain written after the current head of the flow.
+    pub fn new(reader: GrainReader, depth: usize) -> Result<Self> {
+        if depth == 0 {
+            return Err(Error::InvalidArg);
+        }
+        let next_index = reader.head_index()? + 1;
+        let (sender, grains) = mpsc::sync_channel(depth);
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let thread = std::thread::Builder::new()
+            .name("mxl-grain-prefetch".to_owned())
+            .spawn(move || {
+                for index in next_index.. {
+                    if thread_stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let result = reader
+                        .get_complete_grain_default(index)
+                        .map(|grain| grain.to_owned());
+                    let failed = result.is_err();
+                    // Fails once the consumer is gone.
+                    if sender.send(result).is_err() || failed {
+                        break;
+                    }
+                }
+            })
+            .map_err(|error| {
+                Error::Other(format!(
+                    "Failed to spawn the grain prefetch thread: {error}"
+                ))
+            })?;
+        Ok(Self {
+            grains: Some(grains),
+            stop,
+            thread: Some(thread),
+            next_index,
+        })
+    }
+
+    /// Index of the grain returned by the next call to `next`.
+    pub fn next_index(&self) -> u64 {
+        self.next_index
+    }
+
+    /// Returns the next grain, waiting for it to be fetched if needed.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<OwnedGrainData> {
+        let received = self
+            .grains
+            .as_ref()
+            .and_then(|grains| grains.recv().ok())
+            .ok_or_else(|| Error::Other("Grain prefetching stopped after an error.".to_owned()))?;
+        let grain = received?;
+        self.next_index += 1;
+        Ok(grain)
+    }
+}
+
+/// Stops the background thread, which can take up to the read timeout if it is waiting for a
+/// grain.
+impl Drop for PrefetchingGrainReader {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // Unblocks the thread if it is waiting for room in the channel.
+        drop(self.grains.take());
+        if let Some(thread) = self.thread.take()
+            && thread.join().is_err()
+        {
+            tracing::error!("Grain prefetch thread panicked.");
+        }
+    }
+}
@@ -1,20 +1,46 @@
 // SPDX-FileCopyrightText: 2025 2025 Contributors to the Media eXchange Layer project.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{sync::Arc, time::Duration};
+use std::{
+    ops::Range,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use crate::{
-    Error, FlowConfigInfo, GrainData, Result,
+    Error, FlowConfigInfo, GrainData, GrainIter, GrainSlices, GrainStatus, OwnedGrainData, Result,
+    Timeout, Timestamp, WaitConfig,
+    error::report_drop_error,
     flow::{
         FlowInfo,
-        reader::{get_config_info, get_flow_info, get_runtime_info},
+        reader::{
+            DEFAULT_READ_TIMEOUT, get_config_info, get_flow_info, get_runtime_info,
+            oldest_available_index, wait_for_index,
+        },
     },
     instance::InstanceContext,
 };
 
+/// `MXL_GRAIN_VALID_SLICES_ALL`, which bindgen cannot translate as it is defined with a cast.
+const MXL_GRAIN_VALID_SLICES_ALL: u16 = u16::MAX;
+
+/// Whether `GrainReader::get_grain_with_policy` returns grains whose slices are not all committed
+/// yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrainReadPolicy {
+    /// Wait for the grain to be complete, as `GrainReader::get_complete_grain` does.
+    RequireComplete,
+    /// Return the grain as soon as some of its slices are committed.
+    AcceptPartial,
+    /// Wait for the grain to be complete for at most the given duration, then return it as soon as
+    /// some of its slices are committed. This trades completeness for a bounded latency.
+    AcceptAfter(Duration),
+}
+
 pub struct GrainReader {
-    context: Arc<InstanceContext>,
+    pub(crate) context: Arc<InstanceContext>,
     reader: mxl_sys::FlowReader,
+    default_timeout: Duration,
 }
 
 /// The MXL readers and writers are not thread-safe, so we do not implement `Sync` for them, but
@@ -23,7 +49,11 @@ unsafe impl Send for GrainReader {}
 
 impl GrainReader {
     pub(crate) fn new(context: Arc<InstanceContext>, reader: mxl_sys::FlowReader) -> Self {
-        Self { context, reader }
+        Self {
+            context,
+            reader,
+            default_timeout: DEFAULT_READ_TIMEOUT,
+        }
     }
 
     pub fn destroy(mut self) -> Result<()> {
@@ -44,47 +74,193 @@ impl GrainReader {
         get_runtime_info(&self.context, self.reader)
     }
 
+    /// See `FlowReader::head_index`.
+    pub fn head_index(&self) -> Result<u64> {
+        Ok(self.get_runtime_info()?.headIndex)
+    }
+
+    /// Sets the timeout used by `get_complete_grain_default`, 5 seconds if never set. Reads running
+    /// out of it fail with `Error::Timeout`.
+    pub fn set_default_timeout(&mut self, timeout: Duration) {
+        self.default_timeout = timeout;
+    }
+
+    pub fn default_timeout(&self) -> Duration {
+        self.default_timeout
+    }
+
+    /// See `FlowReader::wait_for_index`.
+    pub fn wait_for_index(&self, index: u64, timeout: Duration) -> Result<()> {
+        wait_for_index(&self.context, self.reader, index, timeout, None)
+    }
+
+    /// See `FlowReader::wait_for_index_with`.
+    pub fn wait_for_index_with(
+        &self,
+        index: u64,
+        timeout: Duration,
+        wait_config: &WaitConfig,
+    ) -> Result<()> {
+        wait_for_index(
+            &self.context,
+            self.reader,
+            index,
+            timeout,
+            Some(wait_config),
+        )
+    }
+
+    /// Reads the complete grain at `index`, waiting for it as long as `timeout` says, see
+    /// `Timeout`.
+    ///
+    /// Returns `Error::Timeout` if the grain is still missing or partial when `timeout` elapses,
+    /// although MXL itself reports it as `Error::OutOfRangeTooEarly`, which is only returned with
+    /// `Timeout::Immediate`.
+    #[cfg_attr(
+        feature = "trace-ffi",
+        tracing::instrument(level = "trace", skip(self, timeout))
+    )]
     pub fn get_complete_grain<'a>(
         &'a self,
         index: u64,
-        timeout: Duration,
+        timeout: impl Into<Timeout>,
     ) -> Result<GrainData<'a>> {
+        let (grain, _) =
+            self.get_grain_with_policy(index, GrainReadPolicy::RequireComplete, timeout)?;
+        Ok(grain)
+    }
+
+    /// Reads the grain at `index`, accepting a partial grain according to `policy`, see
+    /// `GrainReadPolicy`. Returns the grain along with how many of its slices are committed, see
+    /// `GrainSlices`.
+    ///
+    /// Returns `Error::Timeout` if no grain acceptable by `policy` is available before `timeout`
+    /// elapses, or `Error::OutOfRangeTooEarly` right away with `Timeout::Immediate`.
+    pub fn get_grain_with_policy<'a>(
+        &'a self,
+        index: u64,
+        policy: GrainReadPolicy,
+        timeout: impl Into<Timeout>,
+    ) -> Result<(GrainData<'a>, GrainSlices)> {
         let mut grain_info: mxl_sys::GrainInfo = unsafe { std::mem::zeroed() };
         let mut payload_ptr: *mut u8 = std::ptr::null_mut();
-        let timeout_ns = timeout.as_nanos() as u64;
+        let timeout = timeout.into();
+        // The timeout applies to the whole call, not to each attempt, so that a grain whose slices
+        // are committed slowly cannot block us for longer than requested.
+        let start = Instant::now();
+        let deadline = start + timeout.as_ffi_duration();
+        // From this point on, a partial grain is good enough.
+        let partial_deadline = match policy {
+            GrainReadPolicy::RequireComplete => None,
+            GrainReadPolicy::AcceptPartial => Some(start),
+            GrainReadPolicy::AcceptAfter(delay) => Some(start + delay),
+        };
         loop {
-            unsafe {
-                Error::from_status(self.context.api.flow_reader_get_grain(
+            let now = Instant::now();
+            let accept_partial =
+                partial_deadline.is_some_and(|partial_deadline| now >= partial_deadline);
+            // Until a partial grain becomes acceptable, ask MXL for all the slices, and wake up
+            // when it becomes acceptable.
+            let (min_valid_slices, attempt_deadline) = if accept_partial {
+                (1, deadline)
+            } else {
+                (
+                    MXL_GRAIN_VALID_SLICES_ALL,
+                    partial_deadline
+                        .map_or(deadline, |partial_deadline| partial_deadline.min(deadline)),
+                )
+            };
+            let timeout_ns = attempt_deadline.saturating_duration_since(now).as_nanos() as u64;
+            let status = unsafe {
+                self.context.api.flow_reader_get_grain_slice(
                     self.reader,
                     index,
+                    min_valid_slices,
                     timeout_ns,
                     &mut grain_info,
                     &mut payload_ptr,
-                ))?;
-            }
-            if grain_info.validSlices != grain_info.totalSlices {
-                // We don't need partial grains. Wait for the grain to be complete.
-                continue;
+                )
+            };
+            match Error::from_status(status) {
+                Ok(()) => {}
+                // MXL reports a grain still missing at the end of the wait as too early. Before our
+                // deadline, this only means that a partial grain became acceptable.
+                Err(Error::OutOfRangeTooEarly) if Instant::now() < deadline => continue,
+                Err(Error::OutOfRangeTooEarly) if timeout != Timeout::Immediate => {
+                    return Err(Error::Timeout);
+                }
+                Err(error) => return Err(error),
             }
+            let slices = GrainSlices {
+                committed: grain_info.validSlices,
+                total: grain_info.totalSlices,
+            };
             if payload_ptr.is_null() {
                 return Err(Error::Other(format!(
                     "Failed to get grain payload for index {index}.",
                 )));
             }
-            break;
+
+            // SAFETY
+            // We know that the lifetime is as long as the flow, so it is at least self's lifetime.
+            // It may happen that the buffer is overwritten by a subsequent write, but it is safe.
+            let payload =
+                unsafe { std::slice::from_raw_parts(payload_ptr, grain_info.grainSize as usize) };
+
+            return Ok((
+                GrainData {
+                    payload,
+                    total_size: grain_info.grainSize as usize,
+                    flags: grain_info.flags,
+                },
+                slices,
+            ));
         }
+    }
 
-        // SAFETY
-        // We know that the lifetime is as long as the flow, so it is at least self's lifetime.
-        // It may happen that the buffer is overwritten by a subsequent write, but it is safe.
-        let payload =
-            unsafe { std::slice::from_raw_parts(payload_ptr, grain_info.grainSize as usize) };
+    /// Reads the grain due at `timestamp`, converted to an index with the grain rate of the flow,
+    /// and returns it along with its index. Fails with `Error::InvalidArg` if MXL cannot convert
+    /// the timestamp.
+    pub fn get_at_timestamp(
+        &self,
+        timestamp: Timestamp,
+        timeout: Duration,
+    ) -> Result<(u64, OwnedGrainData)> {
+        let rate = self.get_config_info()?.common().grain_rate()?;
+        let index = unsafe {
+            self.context
+                .api
+                .timestamp_to_index(&rate, timestamp.as_nanos())
+        };
+        if index == u64::MAX {
+            return Err(Error::InvalidArg);
+        }
+        let grain = self.get_complete_grain(index, timeout)?;
+        Ok((index, grain.to_owned()))
+    }
 
-        Ok(GrainData {
-            payload,
-            total_size: grain_info.grainSize as usize,
-            flags: grain_info.flags,
-        })
+    /// Same as `get_complete_grain`, using the timeout set with `set_default_timeout`.
+    pub fn get_complete_grain_default(&self, index: u64) -> Result<GrainData<'_>> {
+        self.get_complete_grain(index, self.default_timeout)
+    }
+
+    /// Same as `get_complete_grain`, also checking that the CRC-32 of the payload (see
+    /// `GrainData::crc32`) is `expected`, e.g. to track down shared memory corruption. Fails with
+    /// `Error::ChecksumMismatch` otherwise.
+    ///
+    /// MXL grains carry no user data, so the producer has to convey the checksums out of band.
+    pub fn get_complete_grain_verified(
+        &self,
+        index: u64,
+        timeout: Duration,
+        expected: u32,
+    ) -> Result<GrainData<'_>> {
+        let grain = self.get_complete_grain(index, timeout)?;
+        let actual = grain.crc32();
+        if actual != expected {
+            return Err(Error::ChecksumMismatch { expected, actual });
+        }
+        Ok(grain)
     }
 
     /// Non-blocking version of `get_complete_grain`. If the grain is not available, returns an error.
@@ -120,6 +296,146 @@ impl GrainReader {
         })
     }
 
+    /// Same as `get_grain_non_blocking`, but returns `Ok(None)` instead of
+    /// `Error::OutOfRangeTooEarly` if the writer did not reach the grain yet, so that only genuine
+    /// failures are errors.
+    pub fn try_get_grain(&self, index: u64) -> Result<Option<GrainData<'_>>> {
+        match self.get_grain_non_blocking(index) {
+            Ok(grain) => Ok(Some(grain)),
+            Err(Error::OutOfRangeTooEarly) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Reports the state of the grain without waiting, so that latency-sensitive readers can
+    /// decide themselves whether to use a partial grain or to wait for it. A partial grain can be
+    /// read with `get_grain_with_policy` and `GrainReadPolicy::AcceptPartial`.
+    pub fn get_grain_status(&self, index: u64) -> Result<GrainStatus> {
+        let mut grain_info: mxl_sys::GrainInfo = unsafe { std::mem::zeroed() };
+        let mut payload_ptr: *mut u8 = std::ptr::null_mut();
+        // With a single valid slice required, MXL returns partial grains too.
+        let status = unsafe {
+            self.context.api.flow_reader_get_grain_slice_non_blocking(
+                self.reader,
+                index,
+                1,
+                &mut grain_info,
+                &mut payload_ptr,
+            )
+        };
+        match Error::from_status(status) {
+            Ok(()) => {}
+            Err(Error::OutOfRangeTooEarly) => return Ok(GrainStatus::NotReady),
+            Err(error) => return Err(error),
+        }
+        if grain_info.validSlices != grain_info.totalSlices {
+            return Ok(GrainStatus::Partial {
+                committed: grain_info.validSlices,
+                total: grain_info.totalSlices,
+            });
+        }
+        if payload_ptr.is_null() {
+            return Err(Error::Other(format!(
+                "Failed to get grain payload for index {index}.",
+            )));
+        }
+
+        // SAFETY
+        // The payload is copied right away, while the reader is borrowed.
+        let payload =
+            unsafe { std::slice::from_raw_parts(payload_ptr, grain_info.grainSize as usize) };
+        Ok(GrainStatus::Complete(OwnedGrainData {
+            payload: payload.to_vec(),
+            flags: grain_info.flags,
+        }))
+    }
+
+    /// Reads the newest complete grain of the flow and returns it along with its index. This is the
+    /// grain at the head of the flow, or the one before it if the writer is still filling the head.
+    ///
+    /// If the writer moves so far ahead between the head query and the read that the grain left
+    /// the ring buffer, the head is queried again once.
+    pub fn latest(&self, timeout: Duration) -> Result<(u64, OwnedGrainData)> {
+        match self.read_latest(timeout) {
+            Err(Error::OutOfRangeTooLate) => self.read_latest(timeout),
+            result => result,
+        }
+    }
+
+    /// Same as `get_complete_grain`, but recovers from the reader falling behind: if the grain at
+    /// `index` already left the ring buffer, reads the newest complete grain instead, see
+    /// `latest`. Returns the index of the grain actually read, which the caller should continue
+    /// from.
+    pub fn read_resync(&self, index: u64, timeout: Duration) -> Result<(u64, OwnedGrainData)> {
+        match self.get_complete_grain(index, timeout) {
+            Ok(grain) => Ok((index, grain.to_owned())),
+            Err(Error::OutOfRangeTooLate) => {
+                let (head_index, grain) = self.latest(timeout)?;
+                tracing::warn!(
+                    "Grain reader fell behind, skipped grains {index} to {}.",
+                    head_index.saturating_sub(1)
+                );
+                Ok((head_index, grain))
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Reads the grain `behind` grains before the head of the flow, e.g. for a monitor running at
+    /// a fixed latency, and returns it along with its index. `behind` is clamped so that the grain
+    /// is still in the ring buffer, see `FlowReader::oldest_available_index`. With `behind` set to
+    /// 0, this reads the head grain, waiting for it to be complete if the writer is still filling
+    /// it.
+    pub fn get_relative(&self, behind: u64, timeout: Duration) -> Result<(u64, OwnedGrainData)> {
+        let info = self.get_info()?;
+        let index = info
+            .runtime
+            .head_index()
+            .saturating_sub(behind)
+            .max(oldest_available_index(&info)?);
+        let grain = self.get_complete_grain(index, timeout)?;
+        Ok((index, grain.to_owned()))
+    }
+
+    fn read_latest(&self, timeout: Duration) -> Result<(u64, OwnedGrainData)> {
+        let head_index = self.head_index()?;
+        if let GrainStatus::Complete(grain) = self.get_grain_status(head_index)? {
+            return Ok((head_index, grain));
+        }
+        let index = head_index.checked_sub(1).ok_or(Error::OutOfRangeTooEarly)?;
+        let grain = self.get_complete_grain(index, timeout)?;
+        Ok((index, grain.to_owned()))
+    }
+
+    /// Returns an iterator reading the grains in `range` one by one, each with the given timeout.
+    /// This makes it easy to `collect()` a segment of a flow for offline processing.
+    pub fn grains(&self, range: Range<u64>, timeout: Duration) -> GrainIter<'_> {
+        GrainIter::new(self, range, timeout)
+    }
+
+    /// Same as `get_complete_grain`, copying the payload into `buf`. The previous content of `buf`
+    /// is discarded, and its allocation is reused if large enough, so that e.g. a recording loop
+    /// can read all the grains into the same buffer.
+    pub fn read_into(&self, index: u64, timeout: Duration, buf: &mut Vec<u8>) -> Result<()> {
+        let grain = self.get_complete_grain(index, timeout)?;
+        buf.clear();
+        buf.extend_from_slice(grain.payload);
+        Ok(())
+    }
+
+    /// Reads the grains at the given, possibly non-contiguous indices (e.g. keyframes only), each
+    /// with the given timeout. The results are in the order of `indices`, and a failure to read
+    /// one grain does not prevent reading the others.
+    pub fn read_batch(&self, indices: &[u64], timeout: Duration) -> Vec<Result<OwnedGrainData>> {
+        indices
+            .iter()
+            .map(|index| {
+                self.get_complete_grain(*index, timeout)
+                    .map(|grain| grain.to_owned())
+            })
+            .collect()
+    }
+
     fn destroy_inner(&mut self) -> Result<()> {
         if self.reader.is_null() {
             return Err(Error::InvalidArg);
@@ -142,6 +458,7 @@ impl Drop for GrainReader {
             && let Err(err) = self.destroy_inner()
         {
             tracing::error!("Failed to release MXL flow reader (discrete): {:?}", err);
+            report_drop_error(&err);
         }
     }
 }
@@ -0,0 +1,46 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::{Arc, Mutex};
+
+use crate::{Error, GrainWriter, Result};
+
+/// A `GrainWriter` that can be cloned and shared between threads or tasks, e.g. to let several
+/// producers submit grains to the same flow.
+///
+/// MXL serializes the grain writes of a flow anyway, the mutex only makes the non-`Sync` writer
+/// safe to use from several threads on the Rust level.
+#[derive(Clone)]
+pub struct SharedGrainWriter {
+    writer: Arc<Mutex<GrainWriter>>,
+}
+
+impl SharedGrainWriter {
+    pub fn new(writer: GrainWriter) -> Self {
+        Self {
+            writer: Arc::new(Mutex::new(writer)),
+        }
+    }
+
+    /// See `GrainWriter::write_grain`.
+    pub fn write_grain(&self, index: u64, payload: &[u8]) -> Result<()> {
+        self.writer
+            .lock()
+            .map_err(|_| Error::Other("Grain writer mutex poisoned.".to_owned()))?
+            .write_grain(index, payload)
+    }
+
+    /// Runs `f` with exclusive access to the writer, e.g. to write a grain slice by slice.
+    pub fn with_writer<T>(&self, f: impl FnOnce(&GrainWriter) -> T) -> Result<T> {
+        let writer = self
+            .writer
+            .lock()
+            .map_err(|_| Error::Other("Grain writer mutex poisoned.".to_owned()))?;
+        Ok(f(&writer))
+    }
+
+    /// Returns the inner writer if this is the last reference to it.
+    pub fn try_into_inner(self) -> Option<GrainWriter> {
+        Arc::into_inner(self.writer)?.into_inner().ok()
+    }
+}
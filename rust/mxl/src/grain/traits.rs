@@ -0,0 +1,98 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use crate::{GrainReader, GrainWriter, OwnedGrainData, Result, SharedGrainWriter, Timeout};
+
+/// Something grains can be read from, implemented by `GrainReader`, and by `mock::MockGrainFlow`
+/// with the `mock` feature, so that code reading grains can be tested without MXL.
+///
+/// The methods forward to the inherent ones. References, `Box` and `Arc` of a source are sources
+/// too, so that pipeline stages generic over `GrainSource` can borrow or share it.
+pub trait GrainSource {
+    /// Reads a copy of the complete grain at `index`, waiting for it as long as `timeout` says,
+    /// see `GrainReader::get_complete_grain`.
+    fn read_grain(&self, index: u64, timeout: Timeout) -> Result<OwnedGrainData>;
+
+    /// Index of the last grain written, see `FlowReader::head_index`.
+    fn head_index(&self) -> Result<u64>;
+}
+
+/// Something grains can be written to, implemented by `GrainWriter` and `SharedGrainWriter`, and
+/// by `mock::MockGrainFlow` with the `mock` feature. As for `GrainSource`, references, `Box` and
+/// `Arc` of a sink are sinks too.
+pub trait GrainSink {
+    /// Writes `payload` as the complete grain at `index`, see `GrainWriter::write_grain`.
+    fn write_grain(&self, index: u64, payload: &[u8]) -> Result<()>;
+}
+
+impl GrainSource for GrainReader {
+    fn read_grain(&self, index: u64, timeout: Timeout) -> Result<OwnedGrainData> {
+        Ok(self.get_complete_grain(index, timeout)?.to_owned())
+    }
+
+    fn head_index(&self) -> Result<u64> {
+        GrainReader::head_index(self)
+    }
+}
+
+impl GrainSink for GrainWriter {
+    fn write_grain(&self, index: u64, payload: &[u8]) -> Result<()> {
+        GrainWriter::write_grain(self, index, payload)
+    }
+}
+
+impl GrainSink for SharedGrainWriter {
+    fn write_grain(&self, index: u64, payload: &[u8]) -> Result<()> {
+        SharedGrainWriter::write_grain(self, index, payload)
+    }
+}
+
+impl<T: GrainSource + ?Sized> GrainSource for &T {
+    fn read_grain(&self, index: u64, timeout: Timeout) -> Result<OwnedGrainData> {
+        (**self).read_grain(index, timeout)
+    }
+
+    fn head_index(&self) -> Result<u64> {
+        (**self).head_index()
+    }
+}
+
+impl<T: GrainSource + ?Sized> GrainSource for Box<T> {
+    fn read_grain(&self, index: u64, timeout: Timeout) -> Result<OwnedGrainData> {
+        (**self).read_grain(index, timeout)
+    }
+
+    fn head_index(&self) -> Result<u64> {
+        (**self).head_index()
+    }
+}
+
+impl<T: GrainSource + ?Sized> GrainSource for Arc<T> {
+    fn read_grain(&self, index: u64, timeout: Timeout) -> Result<OwnedGrainData> {
+        (**self).read_grain(index, timeout)
+    }
+
+    fn head_index(&self) -> Result<u64> {
+        (**self).head_index()
+    }
+}
+
+impl<T: GrainSink + ?Sized> GrainSink for &T {
+    fn write_grain(&self, index: u64, payload: &[u8]) -> Result<()> {
+        (**self).write_grain(index, payload)
+    }
+}
+
+impl<T: GrainSink + ?Sized> GrainSink for Box<T> {
+    fn write_grain(&self, index: u64, payload: &[u8]) -> Result<()> {
+        (**self).write_grain(index, payload)
+    }
+}
+
+impl<T: GrainSink + ?Sized> GrainSink for Arc<T> {
+    fn write_grain(&self, index: u64, payload: &[u8]) -> Result<()> {
+        (**self).write_grain(index, payload)
+    }
+}
@@ -1,11 +1,11 @@
 // SPDX-FileCopyrightText: 2025 2025 Contributors to the Media eXchange Layer project.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{marker::PhantomData, sync::Arc};
+use std::{marker::PhantomData, ptr::NonNull, sync::Arc};
 
 use tracing::error;
 
-use crate::{Error, Result, instance::InstanceContext};
+use crate::{Error, Result, error::report_drop_error, instance::InstanceContext};
 
 /// RAII grain writing session
 ///
@@ -14,7 +14,10 @@ pub struct GrainWriteAccess<'a> {
     context: Arc<InstanceContext>,
     writer: mxl_sys::FlowWriter,
     grain_info: mxl_sys::GrainInfo,
-    payload_ptr: *mut u8,
+    /// Never null, so that the payload slice can always be built.
+    payload_ptr: NonNull<u8>,
+    /// Number of slices already made visible to readers by `commit_slices`.
+    committed_slices: u16,
     /// Serves as a flag to know whether to cancel the grain on drop.
     committed_or_canceled: bool,
     phantom: PhantomData<&'a ()>,
@@ -25,13 +28,14 @@ impl<'a> GrainWriteAccess<'a> {
         context: Arc<InstanceContext>,
         writer: mxl_sys::FlowWriter,
         grain_info: mxl_sys::GrainInfo,
-        payload_ptr: *mut u8,
+        payload_ptr: NonNull<u8>,
     ) -> Self {
         Self {
             context,
             writer,
             grain_info,
             payload_ptr,
+            committed_slices: 0,
             committed_or_canceled: false,
             phantom: Default::default(),
         }
@@ -39,7 +43,28 @@ impl<'a> GrainWriteAccess<'a> {
 
     pub fn payload_mut(&mut self) -> &mut [u8] {
         unsafe {
-            std::slice::from_raw_parts_mut(self.payload_ptr, self.grain_info.grainSize as usize)
+            std::slice::from_raw_parts_mut(
+                self.payload_ptr.as_ptr(),
+                self.grain_info.grainSize as usize,
+            )
+        }
+    }
+
+    /// Copies `src` to the beginning of the grain payload and returns the number of bytes copied.
+    /// Fails with `Error::InvalidArg` if `src` does not fit into the grain.
+    pub fn write_payload(&mut self, src: &[u8]) -> Result<usize> {
+        let payload = self.payload_mut();
+        if src.len() > payload.len() {
+            return Err(Error::InvalidArg);
+        }
+        payload[..src.len()].copy_from_slice(src);
+        Ok(src.len())
+    }
+
+    /// Fills the whole grain payload with the values returned by `f` for each byte offset.
+    pub fn fill_with(&mut self, mut f: impl FnMut(usize) -> u8) {
+        for (i, byte) in self.payload_mut().iter_mut().enumerate() {
+            *byte = f(i);
         }
     }
 
@@ -51,24 +76,90 @@ impl<'a> GrainWriteAccess<'a> {
         self.grain_info.totalSlices
     }
 
-    pub fn commit(mut self, valid_slices: u16) -> Result<()> {
-        self.committed_or_canceled = true;
+    /// Commits the first `up_to` slices of the grain without finishing the write, so that readers
+    /// can pick up the partial grain while the rest of it is still being produced. The grain stays
+    /// open and has to be finished by `commit` (or canceled) as usual. Committing all the slices
+    /// completes the grain, the final `commit` is then a no-op.
+    ///
+    /// `up_to` must not decrease between calls and must not exceed `total_slices`, otherwise
+    /// `Error::InvalidArg` is returned.
+    pub fn commit_slices(&mut self, up_to: u16) -> Result<()> {
+        if up_to < self.committed_slices || up_to > self.grain_info.totalSlices {
+            return Err(Error::InvalidArg);
+        }
+        self.grain_info.validSlices = up_to;
 
+        unsafe {
+            Error::from_status(
+                self.context
+                    .api
+                    .flow_writer_commit_grain(self.writer, &self.grain_info),
+            )?;
+        }
+        self.committed_slices = up_to;
+        Ok(())
+    }
+
+    /// Makes the payload written so far visible to readers in other threads or processes before
+    /// a commit, with a release fence.
+    ///
+    /// For host memory payloads, this is what the commit does anyway, so calling it is never
+    /// required. For device memory payloads, MXL provides no synchronization: the caller has to
+    /// wait for the device to finish writing the payload (e.g. synchronize its stream) before
+    /// committing, which this cannot do. The `Result` leaves room for MXL-provided
+    /// synchronization.
+    pub fn flush(&self) -> Result<()> {
+        std::sync::atomic::fence(std::sync::atomic::Ordering::Release);
+        Ok(())
+    }
+
+    /// Finishes the write, with the first `valid_slices` slices of the grain valid.
+    ///
+    /// `valid_slices` must not be less than the slices already committed by `commit_slices`, so
+    /// that readers never see the grain shrink, otherwise `Error::InvalidArg` is returned.
+    ///
+    /// If MXL fails to commit the grain, the grain is canceled so that the writer is left in a
+    /// known state, and `Error::CommitFailed` tells whether the cancellation succeeded.
+    #[cfg_attr(
+        feature = "trace-ffi",
+        tracing::instrument(level = "trace", skip(self), fields(index = self.grain_info.index))
+    )]
+    pub fn commit(mut self, valid_slices: u16) -> Result<()> {
         if valid_slices > self.grain_info.totalSlices {
+            // The grain is canceled on drop.
             return Err(Error::Other(format!(
                 "Valid slices {} cannot exceed total slices {}.",
                 valid_slices, self.grain_info.totalSlices
             )));
         }
+        if valid_slices < self.committed_slices {
+            // The grain is canceled on drop.
+            return Err(Error::InvalidArg);
+        }
+        self.committed_or_canceled = true;
+        if self.committed_slices == self.grain_info.totalSlices {
+            // Already completed by `commit_slices`, MXL does not consider the grain open anymore.
+            return Ok(());
+        }
         self.grain_info.validSlices = valid_slices;
 
-        unsafe {
+        let result = unsafe {
             Error::from_status(
                 self.context
                     .api
                     .flow_writer_commit_grain(self.writer, &self.grain_info),
             )
-        }
+        };
+        result.map_err(|error| {
+            let canceled = unsafe {
+                Error::from_status(self.context.api.flow_writer_cancel_grain(self.writer))
+            }
+            .is_ok();
+            Error::CommitFailed {
+                source: Box::new(error),
+                canceled,
+            }
+        })
     }
 
     /// Please note that the behavior of canceling a grain writing is dependent on the behavior
@@ -90,6 +181,7 @@ impl<'a> Drop for GrainWriteAccess<'a> {
             }
         {
             error!("Failed to cancel grain write on drop: {:?}", error);
+            report_drop_error(&error);
         }
     }
 }
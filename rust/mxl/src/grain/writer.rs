@@ -1,16 +1,20 @@
 // SPDX-FileCopyrightText: 2025 2025 Contributors to the Media eXchange Layer project.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::sync::Arc;
+use std::{ptr::NonNull, sync::Arc};
 
 use super::write_access::GrainWriteAccess;
 
-use crate::{Error, Result, instance::InstanceContext};
+use crate::{
+    Error, Result, error::report_drop_error, flow::writer::get_writer_runtime_info,
+    instance::InstanceContext,
+};
 
 /// MXL Flow Writer for discrete flows (grain-based data like video frames)
 pub struct GrainWriter {
     context: Arc<InstanceContext>,
     writer: mxl_sys::FlowWriter,
+    id: uuid::Uuid,
 }
 
 /// The MXL readers and writers are not thread-safe, so we do not implement `Sync` for them, but
@@ -18,18 +22,41 @@ pub struct GrainWriter {
 unsafe impl Send for GrainWriter {}
 
 impl GrainWriter {
-    pub(crate) fn new(context: Arc<InstanceContext>, writer: mxl_sys::FlowWriter) -> Self {
-        Self { context, writer }
+    pub(crate) fn new(
+        context: Arc<InstanceContext>,
+        writer: mxl_sys::FlowWriter,
+        id: uuid::Uuid,
+    ) -> Self {
+        Self {
+            context,
+            writer,
+            id,
+        }
     }
 
     pub fn destroy(mut self) -> Result<()> {
         self.destroy_inner()
     }
 
+    pub fn id(&self) -> uuid::Uuid {
+        self.id
+    }
+
+    /// Runtime info of the written flow, e.g. to check through `lastReadTime` whether anybody is
+    /// reading the flow. MXL provides no such query for writers, so a temporary reader is created
+    /// for each call, which makes it too expensive to call for every grain.
+    pub fn get_runtime_info(&self) -> Result<mxl_sys::FlowRuntimeInfo> {
+        get_writer_runtime_info(&self.context, self.id)
+    }
+
     /// The current MXL implementation states a TODO to allow multiple grains to be edited at the
     /// same time. For this reason, there is no protection on the Rust level against trying to open
     /// multiple grains. If the TODO ever gets removed, it may be worth considering pattern where
     /// opening grain would consume the writer and then return it back on commit or cancel.
+    #[cfg_attr(
+        feature = "trace-ffi",
+        tracing::instrument(level = "trace", skip(self))
+    )]
     pub fn open_grain<'a>(&'a self, index: u64) -> Result<GrainWriteAccess<'a>> {
         let mut grain_info: mxl_sys::GrainInfo = unsafe { std::mem::zeroed() };
         let mut payload_ptr: *mut u8 = std::ptr::null_mut();
@@ -42,11 +69,9 @@ impl GrainWriter {
             ))?;
         }
 
-        if payload_ptr.is_null() {
-            return Err(Error::Other(format!(
-                "Failed to open grain payload for index {index}.",
-            )));
-        }
+        let payload_ptr = NonNull::new(payload_ptr).ok_or_else(|| {
+            Error::Other(format!("Failed to open grain payload for index {index}."))
+        })?;
 
         Ok(GrainWriteAccess::new(
             self.context.clone(),
@@ -56,6 +81,70 @@ impl GrainWriter {
         ))
     }
 
+    /// Opens several grains at once, e.g. for an encoder producing several frames together, so that
+    /// all of them can be filled before being committed.
+    ///
+    /// The current MXL implementation tracks a single open grain per writer: opening a grain
+    /// silently replaces the previously opened one, whose commit then fails with
+    /// `Error::InvalidArg`. Until this changes (see `open_grain`), asking for more than one grain
+    /// fails with `Error::Conflict` instead of handing out grains that cannot be committed.
+    pub fn open_grains(&self, indices: &[u64]) -> Result<Vec<GrainWriteAccess<'_>>> {
+        if indices.len() > 1 {
+            return Err(Error::Conflict);
+        }
+        indices
+            .iter()
+            .map(|index| self.open_grain(*index))
+            .collect()
+    }
+
+    /// Writes a whole grain in one call: opens the grain, copies `payload`, which must be exactly
+    /// the grain size, and commits all the slices. The grain is canceled if anything fails. Use
+    /// `open_grain` for slice-level control.
+    ///
+    /// MXL grains do not carry user data, so there is nothing to set besides the payload.
+    pub fn write_grain(&self, index: u64, payload: &[u8]) -> Result<()> {
+        let mut grain_write_access = self.open_grain(index)?;
+        if payload.len() != grain_write_access.max_size() as usize {
+            // Dropping the access cancels the grain.
+            return Err(Error::InvalidArg);
+        }
+        grain_write_access.write_payload(payload)?;
+        let total_slices = grain_write_access.total_slices();
+        grain_write_access.commit(total_slices)
+    }
+
+    /// Drives a generative source, e.g. a test pattern: writes `count` grains, or runs forever if
+    /// `None`, starting at `start_index`. `f` fills the whole payload of each grain given its
+    /// index, then the grain is committed and the call sleeps until the time of the next index.
+    pub fn run_source(
+        &self,
+        start_index: u64,
+        rate: &mxl_sys::Rational,
+        count: Option<u64>,
+        mut f: impl FnMut(u64, &mut [u8]),
+    ) -> Result<()> {
+        let end_index = count.map(|count| start_index.saturating_add(count));
+        let mut index = start_index;
+        while end_index.is_none_or(|end_index| index < end_index) {
+            let mut grain_write_access = self.open_grain(index)?;
+            f(index, grain_write_access.payload_mut());
+            let total_slices = grain_write_access.total_slices();
+            grain_write_access.commit(total_slices)?;
+
+            index += 1;
+            let sleep_ns = unsafe { self.context.api.get_ns_until_index(index, rate) };
+            if sleep_ns == u64::MAX {
+                return Err(Error::Other(format!(
+                    "Failed to get duration until index, invalid rate {}/{}.",
+                    rate.numerator, rate.denominator
+                )));
+            }
+            unsafe { self.context.api.sleep_for_ns(sleep_ns) };
+        }
+        Ok(())
+    }
+
     fn destroy_inner(&mut self) -> Result<()> {
         if self.writer.is_null() {
             return Err(Error::InvalidArg);
@@ -78,6 +167,7 @@ impl Drop for GrainWriter {
             && let Err(err) = self.destroy_inner()
         {
             tracing::error!("Failed to release MXL flow writer (discrete): {:?}", err);
+            report_drop_error(&err);
         }
     }
 }
@@ -1,9 +1,27 @@
 // SPDX-FileCopyrightText: 2025 2025 Contributors to the Media eXchange Layer project.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{ffi::CString, sync::Arc};
+use std::{
+    ffi::CString,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
-use crate::{Error, FlowConfigInfo, FlowReader, FlowWriter, Result, api::MxlApiHandle};
+use uuid::Uuid;
+
+use crate::{
+    CreatedFlow, Error, FlowConfigInfo, FlowEvent, FlowId, FlowMetrics, FlowReader, FlowWatcher,
+    FlowWriter, RationalExt, Result, Timecode, Timestamp, Version, WriterOptions,
+    api::{MxlApiHandle, library_version},
+    flowdef::FlowDef,
+};
+
+/// Suffix of the flow directories inside an MXL domain.
+const FLOW_DIRECTORY_SUFFIX: &str = ".mxl-flow";
+
+/// Option key of the ring buffer depth, in nanoseconds (see `docs/Configuration.md`).
+const HISTORY_DURATION_OPTION: &str = "urn:x-mxl:option:history_duration/v1.0";
 
 /// This struct stores the context that is shared by all objects.
 /// It is separated out from `MxlInstance` so that it can be cloned
@@ -12,6 +30,9 @@ use crate::{Error, FlowConfigInfo, FlowReader, FlowWriter, Result, api::MxlApiHa
 pub(crate) struct InstanceContext {
     pub(crate) api: MxlApiHandle,
     pub(crate) instance: mxl_sys::Instance,
+    pub(crate) domain: String,
+    /// The options the instance was created with, kept to recreate it in `reload_api`.
+    pub(crate) options: String,
 }
 
 // Allow sharing the context across threads and tasks freely.
@@ -40,25 +61,98 @@ impl Drop for InstanceContext {
     }
 }
 
+#[cfg_attr(
+    feature = "trace-ffi",
+    tracing::instrument(level = "trace", skip(context))
+)]
 pub(crate) fn create_flow_reader(
     context: &Arc<InstanceContext>,
-    flow_id: &str,
+    flow_id: FlowId,
+    options: &str,
 ) -> Result<FlowReader> {
-    let flow_id = CString::new(flow_id)?;
-    let options = CString::new("")?;
+    let flow_id_c = flow_id.to_c_string();
+    let options_c = CString::new(options)?;
     let mut reader: mxl_sys::FlowReader = std::ptr::null_mut();
     unsafe {
         Error::from_status(context.api.create_flow_reader(
             context.instance,
-            flow_id.as_ptr(),
-            options.as_ptr(),
+            flow_id_c.as_ptr(),
+            options_c.as_ptr(),
             &mut reader,
         ))?;
     }
     if reader.is_null() {
         return Err(Error::Other("Failed to create flow reader.".to_string()));
     }
-    Ok(FlowReader::new(context.clone(), reader))
+    Ok(FlowReader::new(
+        context.clone(),
+        reader,
+        flow_id.into(),
+        options.to_owned(),
+    ))
+}
+
+pub(crate) fn get_flow_def(context: &InstanceContext, flow_id: FlowId) -> Result<String> {
+    let flow_id_c = flow_id.to_c_string();
+    const INITIAL_BUFFER_SIZE: usize = 4096;
+    // The flow definition could be replaced by a larger one between the calls, so we grow the
+    // buffer more than once, but not forever.
+    const MAX_ATTEMPTS: usize = 4;
+    let mut buffer: Vec<u8> = vec![0; INITIAL_BUFFER_SIZE];
+
+    for _ in 0..MAX_ATTEMPTS {
+        let mut buffer_size = buffer.len();
+        let status = unsafe {
+            context.api.get_flow_def(
+                context.instance,
+                flow_id_c.as_ptr(),
+                buffer.as_mut_ptr() as *mut std::os::raw::c_char,
+                &mut buffer_size,
+            )
+        };
+
+        // MXL reports the required size along with `MXL_ERR_INVALID_ARG` if the buffer is too
+        // small. If the reported size is not larger, the error has another cause.
+        if status == mxl_sys::MXL_ERR_INVALID_ARG && buffer_size > buffer.len() {
+            let new_len = buffer_size.max(buffer.len() * 2);
+            buffer = vec![0; new_len];
+            continue;
+        }
+        Error::from_status(status)?;
+
+        if buffer_size > 0 && buffer[buffer_size - 1] == 0 {
+            buffer_size -= 1;
+        }
+        buffer.truncate(buffer_size);
+        return Ok(String::from_utf8(buffer)?);
+    }
+
+    Err(Error::Other(format!(
+        "Failed to get the definition of flow {flow_id}: it kept growing past {} bytes.",
+        buffer.len()
+    )))
+}
+
+/// Converts the flow ids given to the `MxlInstance` methods, see `FlowId`.
+fn to_flow_id(flow_id: impl TryInto<FlowId, Error: Into<Error>>) -> Result<FlowId> {
+    flow_id.try_into().map_err(Into::into)
+}
+
+/// Ids of the flows found in the domain directory.
+pub(crate) fn list_flow_ids(domain: &str) -> Result<Vec<Uuid>> {
+    let entries = std::fs::read_dir(domain).map_err(|error| {
+        Error::Other(format!("Failed to list MXL domain \"{domain}\": {error}"))
+    })?;
+    Ok(entries
+        .flatten()
+        .filter_map(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_suffix(FLOW_DIRECTORY_SUFFIX))
+                .and_then(|id| Uuid::parse_str(id).ok())
+        })
+        .collect())
 }
 
 #[derive(Clone)]
@@ -77,20 +171,84 @@ impl MxlInstance {
         if instance.is_null() {
             Err(Error::Other("Failed to create MXL instance.".to_string()))
         } else {
-            let context = Arc::new(InstanceContext { api, instance });
+            let context = Arc::new(InstanceContext {
+                api,
+                instance,
+                domain: domain.to_owned(),
+                options: options.to_owned(),
+            });
             Ok(Self { context })
         }
     }
 
-    pub fn create_flow_reader(&self, flow_id: &str) -> Result<FlowReader> {
-        create_flow_reader(&self.context, flow_id)
+    /// Same as `new`, first creating the domain directory (and its parents) if it does not exist.
+    pub fn new_creating_domain(api: MxlApiHandle, domain: &str, options: &str) -> Result<Self> {
+        std::fs::create_dir_all(domain).map_err(|error| {
+            Error::Other(format!(
+                "Failed to create MXL domain directory \"{domain}\": {error}"
+            ))
+        })?;
+        Self::new(api, domain, options)
+    }
+
+    /// Loads the MXL library from `path` and recreates the instance on the same domain and with the
+    /// same options using it, e.g. to switch to an upgraded library without restarting. The
+    /// flows of the domain persist. Returns the newly loaded API.
+    ///
+    /// All the readers and writers, and all the clones of this instance, must have been dropped
+    /// first, as they use the current library. Otherwise fails with `Error::Conflict` and the
+    /// instance is left untouched, as it is if loading the library fails.
+    pub fn reload_api(&mut self, path: impl AsRef<Path>) -> Result<MxlApiHandle> {
+        if Arc::strong_count(&self.context) > 1 {
+            return Err(Error::Conflict);
+        }
+        let api = crate::load_api(path)?;
+        // The previous instance is destroyed once replaced, with the previous library.
+        *self = Self::new(api.clone(), &self.context.domain, &self.context.options)?;
+        Ok(api)
+    }
+
+    /// The path of the domain directory, as given at creation.
+    pub fn domain(&self) -> &str {
+        &self.context.domain
+    }
+
+    /// The instance options JSON, as given at creation.
+    pub fn options(&self) -> &str {
+        &self.context.options
+    }
+
+    /// Starts building an instance with typed options, see `InstanceBuilder`.
+    pub fn builder(api: MxlApiHandle) -> InstanceBuilder {
+        InstanceBuilder {
+            api,
+            domain: None,
+            options: serde_json::Map::new(),
+        }
     }
 
-    pub fn create_flow_writer(
+    pub fn create_flow_reader(
         &self,
-        flow_def: &str,
-        options: Option<&str>,
-    ) -> Result<(FlowWriter, FlowConfigInfo, bool)> {
+        flow_id: impl TryInto<FlowId, Error: Into<Error>>,
+    ) -> Result<FlowReader> {
+        self.create_flow_reader_with_options(flow_id, "")
+    }
+
+    /// Same as `create_flow_reader`, passing the reader `options` JSON to MXL. The valid option
+    /// keys depend on the MXL build, options unknown to it are ignored.
+    pub fn create_flow_reader_with_options(
+        &self,
+        flow_id: impl TryInto<FlowId, Error: Into<Error>>,
+        options: &str,
+    ) -> Result<FlowReader> {
+        create_flow_reader(&self.context, to_flow_id(flow_id)?, options)
+    }
+
+    #[cfg_attr(
+        feature = "trace-ffi",
+        tracing::instrument(level = "trace", skip_all, fields(flow_id))
+    )]
+    pub fn create_flow_writer(&self, flow_def: &str, options: Option<&str>) -> Result<CreatedFlow> {
         let flow_def = CString::new(flow_def)?;
         let options = options.map(CString::new).transpose()?;
         let mut writer: mxl_sys::FlowWriter = std::ptr::null_mut();
@@ -111,54 +269,76 @@ impl MxlInstance {
         }
 
         let info = unsafe { info_unsafe.assume_init() };
+        #[cfg(feature = "trace-ffi")]
+        tracing::Span::current().record(
+            "flow_id",
+            tracing::field::display(uuid::Uuid::from_bytes(info.common.id)),
+        );
 
-        Ok((
-            FlowWriter::new(
+        Ok(CreatedFlow {
+            writer: FlowWriter::new(
                 self.context.clone(),
                 writer,
                 uuid::Uuid::from_bytes(info.common.id),
+                info,
             ),
-            FlowConfigInfo { value: info },
+            config: FlowConfigInfo { value: info },
             was_created,
-        ))
+        })
     }
 
-    pub fn get_flow_def(&self, flow_id: &str) -> Result<String> {
-        let flow_id = CString::new(flow_id)?;
-        const INITIAL_BUFFER_SIZE: usize = 4096;
-        let mut buffer: Vec<u8> = vec![0; INITIAL_BUFFER_SIZE];
-        let mut buffer_size = INITIAL_BUFFER_SIZE;
+    /// Same as `create_flow_writer`, with typed options.
+    pub fn create_flow_writer_opts(
+        &self,
+        flow_def: &str,
+        options: &WriterOptions,
+    ) -> Result<CreatedFlow> {
+        self.create_flow_writer(flow_def, Some(&options.as_option_string()?))
+    }
 
-        let status = unsafe {
-            self.context.api.get_flow_def(
+    /// Same as `create_flow_writer`, taking a typed flow definition, which is checked with
+    /// `FlowDef::validate` first.
+    pub fn create_flow_writer_from_def(
+        &self,
+        flow_def: &FlowDef,
+        options: Option<&str>,
+    ) -> Result<CreatedFlow> {
+        flow_def.validate()?;
+        self.create_flow_writer(flow_def.to_json()?.as_str(), options)
+    }
+
+    /// Returns whether the flow currently has an active writer. Fails with `Error::FlowNotFound`
+    /// if the flow does not exist.
+    pub fn is_flow_active(
+        &self,
+        flow_id: impl TryInto<FlowId, Error: Into<Error>>,
+    ) -> Result<bool> {
+        let flow_id = to_flow_id(flow_id)?.to_c_string();
+        let mut is_active = false;
+        unsafe {
+            Error::from_status(self.context.api.is_flow_active(
                 self.context.instance,
                 flow_id.as_ptr(),
-                buffer.as_mut_ptr() as *mut std::os::raw::c_char,
-                &mut buffer_size,
-            )
-        };
-
-        if status == mxl_sys::MXL_ERR_INVALID_ARG && buffer_size > INITIAL_BUFFER_SIZE {
-            buffer = vec![0; buffer_size];
-            unsafe {
-                Error::from_status(self.context.api.get_flow_def(
-                    self.context.instance,
-                    flow_id.as_ptr(),
-                    buffer.as_mut_ptr() as *mut std::os::raw::c_char,
-                    &mut buffer_size,
-                ))?;
-            }
-        } else {
-            Error::from_status(status)?;
+                &mut is_active,
+            ))?;
         }
+        Ok(is_active)
+    }
 
-        if buffer_size > 0 && buffer[buffer_size - 1] == 0 {
-            buffer_size -= 1;
+    /// Cheap check whether a flow exists in the domain, without creating a reader.
+    pub fn flow_exists(&self, flow_id: impl TryInto<FlowId, Error: Into<Error>>) -> Result<bool> {
+        match self.is_flow_active(flow_id) {
+            Ok(_) => Ok(true),
+            Err(Error::FlowNotFound) => Ok(false),
+            Err(error) => Err(error),
         }
-        buffer.truncate(buffer_size);
+    }
 
-        String::from_utf8(buffer)
-            .map_err(|_| Error::Other("Invalid UTF-8 in flow definition".to_string()))
+    pub fn get_flow_def(
+        &self,
+        flow_id: impl TryInto<FlowId, Error: Into<Error>>,
+    ) -> Result<String> {
+        get_flow_def(&self.context, to_flow_id(flow_id)?)
     }
 
     /// Garbage-collect orphan flow directories in the MXL domain.
@@ -178,6 +358,117 @@ impl MxlInstance {
         }
     }
 
+    /// Removes the flow with the given id from the domain. Fails with `Error::Conflict` if the
+    /// flow still has an active writer.
+    ///
+    /// MXL has no per-flow removal, so this removes the flow directory (see the domain layout in
+    /// `docs/Architecture.md`), after the same check for an active writer that
+    /// `garbage_collect_flows` performs.
+    pub fn delete_flow(&self, flow_id: impl TryInto<FlowId, Error: Into<Error>>) -> Result<()> {
+        let flow_id = to_flow_id(flow_id)?;
+        if self.is_flow_active(flow_id)? {
+            return Err(Error::Conflict);
+        }
+        let flow_dir =
+            Path::new(&self.context.domain).join(format!("{flow_id}{FLOW_DIRECTORY_SUFFIX}"));
+        std::fs::remove_dir_all(&flow_dir).map_err(|error| {
+            Error::Other(format!(
+                "Failed to remove flow directory \"{}\": {error}",
+                flow_dir.display()
+            ))
+        })
+    }
+
+    /// Removes all flows without an active writer whose last write is older than `older_than`,
+    /// and returns their ids. Flows with an active writer are never removed, however quiet they
+    /// are. Flows removed concurrently are skipped, as are flows which cannot be inspected, e.g.
+    /// half-created by a crashed writer, after logging the error.
+    pub fn gc_stale_flows(&self, older_than: Duration) -> Result<Vec<Uuid>> {
+        let now = self.get_time();
+        let mut removed = Vec::new();
+        for flow_id in self.list_flow_ids()? {
+            match self.remove_if_stale(flow_id, now, older_than) {
+                Ok(true) => removed.push(flow_id),
+                Ok(false) => {}
+                // The flow was removed since the domain was listed.
+                Err(Error::FlowNotFound) => {}
+                Err(error) => {
+                    tracing::warn!("Skipping flow {flow_id} while collecting stale flows: {error}");
+                }
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Removes the flow if it has no active writer and its last write is older than `older_than`,
+    /// and returns whether it did.
+    fn remove_if_stale(&self, flow_id: Uuid, now: Timestamp, older_than: Duration) -> Result<bool> {
+        if self.is_flow_active(flow_id)? {
+            return Ok(false);
+        }
+        let staleness = self
+            .create_flow_reader(flow_id)?
+            .get_info()?
+            .runtime
+            .staleness(now);
+        if staleness < older_than {
+            return Ok(false);
+        }
+        match self.delete_flow(flow_id) {
+            Ok(()) => Ok(true),
+            // A writer attached in the meantime, the flow is not stale anymore.
+            Err(Error::Conflict) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Snapshot of the runtime state of all the flows in the domain, see `FlowMetrics`. Flows
+    /// removed concurrently are skipped, as are flows which cannot be inspected, e.g. half-created
+    /// by a crashed writer, after logging the error.
+    pub fn metrics(&self) -> Result<Vec<FlowMetrics>> {
+        let now = self.get_time();
+        let mut metrics = Vec::new();
+        for flow_id in self.list_flow_ids()? {
+            let runtime = match self
+                .create_flow_reader(flow_id)
+                .and_then(|reader| reader.get_info())
+            {
+                Ok(info) => info.runtime,
+                // The flow was removed since the domain was listed.
+                Err(Error::FlowNotFound) => continue,
+                Err(error) => {
+                    tracing::warn!("Skipping flow {flow_id} while collecting metrics: {error}");
+                    continue;
+                }
+            };
+            metrics.push(FlowMetrics {
+                flow_id,
+                head_index: runtime.head_index(),
+                last_write_time: Timestamp::from_nanos(runtime.last_write_time()),
+                last_read_time: Timestamp::from_nanos(runtime.last_read_time()),
+                staleness: runtime.staleness(now),
+            });
+        }
+        Ok(metrics)
+    }
+
+    /// Watches the flows of the domain on a dedicated thread, scanning the domain every `poll`
+    /// and calling `callback` for each flow that appeared or disappeared since the previous scan.
+    /// The flows already present when the watch starts are reported as added by the first scan.
+    ///
+    /// The watch runs until the returned `FlowWatcher` is dropped.
+    pub fn watch_flows(
+        &self,
+        poll: Duration,
+        callback: impl FnMut(FlowEvent) + Send + 'static,
+    ) -> Result<FlowWatcher> {
+        FlowWatcher::spawn(self.context.domain.clone(), poll, callback)
+    }
+
+    fn list_flow_ids(&self) -> Result<Vec<Uuid>> {
+        list_flow_ids(&self.context.domain)
+    }
+
     pub fn get_current_index(&self, rational: &mxl_sys::Rational) -> u64 {
         unsafe { self.context.api.get_current_index(rational) }
     }
@@ -198,9 +489,60 @@ impl MxlInstance {
         }
     }
 
-    /// TODO: Make timestamp a strong type.
-    pub fn timestamp_to_index(&self, timestamp: u64, rate: &mxl_sys::Rational) -> Result<u64> {
-        let index = unsafe { self.context.api.timestamp_to_index(rate, timestamp) };
+    /// How long ago the grain or sample at `index` was due, relative to `get_time`. Returns zero
+    /// for an index in the future, see `get_duration_until_index` for those.
+    pub fn duration_since_index(
+        &self,
+        index: u64,
+        rate: &mxl_sys::Rational,
+    ) -> Result<std::time::Duration> {
+        let timestamp = self.index_to_timestamp(index, rate)?;
+        Ok(self.get_time().checked_sub(timestamp).unwrap_or_default())
+    }
+
+    /// Time difference between two indices, whichever comes first, e.g. between the index a
+    /// producer is about to write and the current index, to measure how far its pacing drifted.
+    pub fn index_drift(
+        &self,
+        expected_index: u64,
+        actual_index: u64,
+        rate: &mxl_sys::Rational,
+    ) -> Result<std::time::Duration> {
+        rate.duration_of(expected_index.abs_diff(actual_index))
+            .ok_or_else(|| {
+                Error::Other(format!(
+                    "Failed to compute index drift, invalid rate {}/{}.",
+                    rate.numerator, rate.denominator
+                ))
+            })
+    }
+
+    /// Whether a producer about to write `index` lags behind the current index by more than
+    /// `threshold`, in which case it should skip ahead to the current index rather than try to
+    /// catch up. Being ahead of the current index is not a drift to recover from.
+    pub fn should_resync(
+        &self,
+        index: u64,
+        rate: &mxl_sys::Rational,
+        threshold: std::time::Duration,
+    ) -> Result<bool> {
+        let current_index = self.get_current_index(rate);
+        if index >= current_index {
+            return Ok(false);
+        }
+        Ok(self.index_drift(index, current_index, rate)? > threshold)
+    }
+
+    pub fn timestamp_to_index(
+        &self,
+        timestamp: Timestamp,
+        rate: &mxl_sys::Rational,
+    ) -> Result<u64> {
+        let index = unsafe {
+            self.context
+                .api
+                .timestamp_to_index(rate, timestamp.as_nanos())
+        };
         if index == u64::MAX {
             Err(Error::Other(format!(
                 "Failed to convert timestamp to index, invalid rate {}/{}.",
@@ -211,7 +553,7 @@ impl MxlInstance {
         }
     }
 
-    pub fn index_to_timestamp(&self, index: u64, rate: &mxl_sys::Rational) -> Result<u64> {
+    pub fn index_to_timestamp(&self, index: u64, rate: &mxl_sys::Rational) -> Result<Timestamp> {
         let timestamp = unsafe { self.context.api.index_to_timestamp(rate, index) };
         if timestamp == u64::MAX {
             Err(Error::Other(format!(
@@ -219,16 +561,56 @@ impl MxlInstance {
                 rate.numerator, rate.denominator
             )))
         } else {
-            Ok(timestamp)
+            Ok(Timestamp::from_nanos(timestamp))
         }
     }
 
+    /// The timecode of the grain at `index`, see `Timecode`. The index counts the frames from the
+    /// MXL epoch, which starts at midnight, so the timecode is the time of day of the grain for
+    /// non-drop-frame rates.
+    pub fn index_to_timecode(&self, index: u64, rate: &mxl_sys::Rational) -> Result<Timecode> {
+        Timecode::from_index(index, rate)
+    }
+
+    /// The index of the grain with the given timecode during the first day of the MXL epoch, see
+    /// `Timecode::to_index`.
+    pub fn timecode_to_index(&self, timecode: &Timecode, rate: &mxl_sys::Rational) -> Result<u64> {
+        timecode.to_index(rate)
+    }
+
+    pub fn library_version(&self) -> Result<Version> {
+        library_version(&self.context.api)
+    }
+
     pub fn sleep_for(&self, duration: std::time::Duration) {
         unsafe { self.context.api.sleep_for_ns(duration.as_nanos() as u64) }
     }
 
-    pub fn get_time(&self) -> u64 {
-        unsafe { self.context.api.get_time() }
+    pub fn get_time(&self) -> Timestamp {
+        Timestamp::from_nanos(unsafe { self.context.api.get_time() })
+    }
+
+    /// How far the MXL clock (`get_time`) is ahead of the system clock, e.g. to correlate MXL
+    /// timestamps with log timestamps, see `Timestamp::to_system_time`. With the MXL clock being
+    /// TAI, this is usually the TAI-UTC offset (37 seconds since 2017), or close to zero if the
+    /// system does not know that offset. Returns zero if the MXL clock is behind.
+    ///
+    /// The two clocks are adjusted independently, e.g. by NTP or at leap seconds, so the offset
+    /// drifts and should be sampled again periodically rather than once.
+    pub fn clock_offset(&self) -> Duration {
+        // Sampling the system clock between two reads of the MXL clock halves the error.
+        let before = self.get_time().as_nanos();
+        let system_time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        let after = self.get_time().as_nanos();
+        let mxl_time = Duration::from_nanos(before + after.saturating_sub(before) / 2);
+        mxl_time.saturating_sub(system_time)
+    }
+
+    /// The system time of `timestamp`, using the current `clock_offset`.
+    pub fn timestamp_to_system_time(&self, timestamp: Timestamp) -> SystemTime {
+        timestamp.to_system_time(self.clock_offset())
     }
 
     /// This function forces the destruction of the MXL instance.
@@ -240,4 +622,79 @@ impl MxlInstance {
             .ok_or_else(|| Error::Other("Instance is still in use.".to_string()))?;
         context.destroy()
     }
+
+    /// Same as `destroy`, but gives the instance back along with the error if it cannot be
+    /// destroyed, so that the caller can release the remaining readers and writers and retry.
+    pub fn try_destroy(self) -> std::result::Result<(), (MxlInstance, Error)> {
+        let mut context = Arc::try_unwrap(self.context).map_err(|context| {
+            (
+                MxlInstance { context },
+                Error::Other("Instance is still in use.".to_string()),
+            )
+        })?;
+        match Error::from_status(unsafe { context.api.destroy_instance(context.instance) }) {
+            Ok(()) => {
+                context.instance = std::ptr::null_mut();
+                Ok(())
+            }
+            Err(error) => Err((
+                MxlInstance {
+                    context: Arc::new(context),
+                },
+                error,
+            )),
+        }
+    }
+}
+
+/// Builder of `MxlInstance`, serializing the options into the JSON object MXL expects.
+pub struct InstanceBuilder {
+    api: MxlApiHandle,
+    domain: Option<PathBuf>,
+    options: serde_json::Map<String, serde_json::Value>,
+}
+
+impl InstanceBuilder {
+    /// The path of the MXL domain. The directory must exist.
+    pub fn domain(mut self, domain: impl AsRef<Path>) -> Self {
+        self.domain = Some(domain.as_ref().to_path_buf());
+        self
+    }
+
+    /// Depth of the ring buffers of the flows. Please note that current MXL versions only honour
+    /// this option from the domain's `options.json` file.
+    pub fn history_duration(self, duration: Duration) -> Self {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        self.option(HISTORY_DURATION_OPTION, nanos)
+    }
+
+    /// Sets an arbitrary option, replacing any previous value of the same key.
+    pub fn option(mut self, key: impl Into<String>, value: impl Into<serde_json::Value>) -> Self {
+        self.options.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn build(self) -> Result<MxlInstance> {
+        let domain = self
+            .domain
+            .ok_or_else(|| Error::Other("MXL domain not set.".to_string()))?;
+        if !domain.is_dir() {
+            return Err(Error::Other(format!(
+                "MXL domain \"{}\" is not an existing directory.",
+                domain.display()
+            )));
+        }
+        let domain = domain.to_str().ok_or_else(|| {
+            Error::Other(format!(
+                "MXL domain \"{}\" is not valid UTF-8.",
+                domain.display()
+            ))
+        })?;
+        let options = if self.options.is_empty() {
+            String::new()
+        } else {
+            serde_json::Value::Object(self.options).to_string()
+        };
+        MxlInstance::new(self.api, domain, &options)
+    }
 }
@@ -6,19 +6,44 @@ mod error;
 mod flow;
 mod grain;
 mod instance;
+mod metrics;
 mod samples;
+mod time;
+mod watcher;
 
 pub mod config;
+#[cfg(feature = "mock")]
+pub mod mock;
 
-pub use api::{MxlApi, load_api};
-pub use error::{Error, Result};
-pub use flow::{reader::FlowReader, writer::FlowWriter, *};
+pub use api::{MxlApi, Version, library_version, load_api, load_api_from_candidates};
+pub use error::{Error, Result, set_drop_error_handler};
+pub use flow::{
+    id::FlowId,
+    reader::{FlowReader, Timeout, WaitConfig},
+    writer::{CreatedFlow, FlowWriter, WriterOptions},
+    *,
+};
+#[cfg(feature = "async")]
+pub use grain::async_reader::AsyncGrainReader;
 pub use grain::{
-    data::*, reader::GrainReader, write_access::GrainWriteAccess, writer::GrainWriter,
+    data::*,
+    iter::GrainIter,
+    prefetch::PrefetchingGrainReader,
+    reader::{GrainReadPolicy, GrainReader},
+    shared_writer::SharedGrainWriter,
+    traits::{GrainSink, GrainSource},
+    write_access::GrainWriteAccess,
+    writer::GrainWriter,
 };
-pub use instance::MxlInstance;
+pub use instance::{InstanceBuilder, MxlInstance};
+pub use metrics::FlowMetrics;
+#[cfg(feature = "async")]
+pub use samples::async_reader::AsyncSamplesReader;
 pub const MXL_DATA_FORMAT_GRAIN_SIZE: usize = mxl_sys::MXL_DATA_FORMAT_GRAIN_SIZE as usize;
 pub use mxl_sys::Rational;
 pub use samples::{
-    data::*, reader::SamplesReader, write_access::SamplesWriteAccess, writer::SamplesWriter,
+    caching_reader::CachingSamplesReader, data::*, reader::SamplesReader,
+    stream_reader::SamplesStreamReader, write_access::SamplesWriteAccess, writer::SamplesWriter,
 };
+pub use time::{Timecode, Timestamp};
+pub use watcher::{FlowEvent, FlowWatcher};
@@ -0,0 +1,37 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::Timestamp;
+
+/// Runtime state of a flow, as returned by `MxlInstance::metrics`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlowMetrics {
+    pub flow_id: Uuid,
+    pub head_index: u64,
+    pub last_write_time: Timestamp,
+    pub last_read_time: Timestamp,
+    /// Time since the last write, as of the snapshot.
+    pub staleness: Duration,
+}
+
+impl FlowMetrics {
+    /// Renders the metrics in the Prometheus text exposition format, one line per metric, labeled
+    /// with the flow id. The times are TAI nanoseconds, see `Timestamp`.
+    pub fn to_prometheus(&self) -> String {
+        let flow = self.flow_id;
+        format!(
+            "mxl_flow_head_index{{flow=\"{flow}\"}} {}\n\
+             mxl_flow_last_write_time_nanoseconds{{flow=\"{flow}\"}} {}\n\
+             mxl_flow_last_read_time_nanoseconds{{flow=\"{flow}\"}} {}\n\
+             mxl_flow_staleness_seconds{{flow=\"{flow}\"}} {}\n",
+            self.head_index,
+            self.last_write_time.as_nanos(),
+            self.last_read_time.as_nanos(),
+            self.staleness.as_secs_f64(),
+        )
+    }
+}
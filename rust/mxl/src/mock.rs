@@ -0,0 +1,122 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+//! In-memory stand-ins for the MXL readers and writers, to test code built on `GrainSource` and
+//! `GrainSink` without the MXL library or a domain.
+
+use std::{
+    collections::HashMap,
+    sync::{Condvar, Mutex, MutexGuard},
+    time::Instant,
+};
+
+use crate::{Error, GrainSink, GrainSource, OwnedGrainData, Result, Timeout};
+
+/// An in-memory discrete flow, which is both a `GrainSource` and a `GrainSink`. Share it between
+/// threads with an `Arc` to have a writer and readers on different threads.
+///
+/// It follows the MXL semantics for the grain indices: the head index is the highest index
+/// written, 0 until the first write, and only the last `grain_count` grains up to the head can be
+/// read or written, older ones fail with `Error::OutOfRangeTooLate`. Reading a grain not written
+/// yet waits for it as `GrainReader::get_complete_grain` does: it fails with `Error::Timeout` if
+/// the grain is still missing after the timeout, or right away with `Error::OutOfRangeTooEarly`
+/// with `Timeout::Immediate`.
+pub struct MockGrainFlow {
+    grain_count: u64,
+    state: Mutex<MockState>,
+    written: Condvar,
+}
+
+struct MockState {
+    grains: HashMap<u64, Vec<u8>>,
+    head_index: u64,
+}
+
+impl MockGrainFlow {
+    pub fn new(grain_count: u32) -> Self {
+        Self {
+            grain_count: u64::from(grain_count.max(1)),
+            state: Mutex::new(MockState {
+                grains: HashMap::new(),
+                head_index: 0,
+            }),
+            written: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        // The state is consistent after every update, a panic elsewhere cannot corrupt it.
+        self.state.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    fn is_too_late(&self, state: &MockState, index: u64) -> bool {
+        index.saturating_add(self.grain_count) <= state.head_index
+    }
+}
+
+impl GrainSource for MockGrainFlow {
+    fn read_grain(&self, index: u64, timeout: Timeout) -> Result<OwnedGrainData> {
+        // No deadline waits forever.
+        let deadline = match timeout {
+            Timeout::Immediate => Some(Instant::now()),
+            Timeout::For(duration) => Instant::now().checked_add(duration),
+            Timeout::Forever => None,
+        };
+        let mut state = self.lock();
+        loop {
+            if self.is_too_late(&state, index) {
+                return Err(Error::OutOfRangeTooLate);
+            }
+            if let Some(payload) = state.grains.get(&index) {
+                return Ok(OwnedGrainData {
+                    payload: payload.clone(),
+                    flags: 0,
+                });
+            }
+            state = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining.is_zero() {
+                        return Err(match timeout {
+                            Timeout::Immediate => Error::OutOfRangeTooEarly,
+                            _ => Error::Timeout,
+                        });
+                    }
+                    self.written
+                        .wait_timeout(state, remaining)
+                        .unwrap_or_else(|error| error.into_inner())
+                        .0
+                }
+                None => self
+                    .written
+                    .wait(state)
+                    .unwrap_or_else(|error| error.into_inner()),
+            };
+        }
+    }
+
+    fn head_index(&self) -> Result<u64> {
+        Ok(self.lock().head_index)
+    }
+}
+
+impl GrainSink for MockGrainFlow {
+    fn write_grain(&self, index: u64, payload: &[u8]) -> Result<()> {
+        let mut state = self.lock();
+        if self.is_too_late(&state, index) {
+            return Err(Error::OutOfRangeTooLate);
+        }
+        state.grains.insert(index, payload.to_vec());
+        if index > state.head_index {
+            state.head_index = index;
+            // Forget the grains which left the ring buffer.
+            let grain_count = self.grain_count;
+            state
+                .grains
+                .retain(|kept_index, _| kept_index.saturating_add(grain_count) > index);
+        }
+        drop(state);
+        self.written.notify_all();
+        Ok(())
+    }
+}
@@ -1,7 +1,12 @@
 // SPDX-FileCopyrightText: 2025 2025 Contributors to the Media eXchange Layer project.
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "async")]
+pub mod async_reader;
+pub mod caching_reader;
 pub mod data;
 pub mod reader;
+pub mod stream_reader;
+mod wav;
 pub mod write_access;
 pub mod writer;
@@ -0,0 +1,83 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use crate::{Error, OwnedSamplesData, Result, SamplesReader};
+
+/// Async wrapper over [`SamplesReader`], reading consecutive batches of samples starting at the
+/// flow head.
+///
+/// `SamplesReader` is `Send` but not `Sync`, so it is kept behind a mutex and each read is executed
+/// on the tokio blocking pool. This allows many flows to be serviced from a single runtime without
+/// dedicating a thread to each of them.
+///
+/// The futures returned by `next_samples` are cancel-safe. Dropping a future before it completes
+/// lets the blocking read finish in the background and does not advance the read index, so the
+/// next call will read the same batch again.
+pub struct AsyncSamplesReader {
+    reader: Arc<Mutex<SamplesReader>>,
+    /// Index of the last sample that was already returned.
+    last_index: AtomicU64,
+    timeout: Duration,
+}
+
+impl AsyncSamplesReader {
+    /// Creates the wrapper. The first batch returned by `next_samples` starts right after the
+    /// current head of the flow. `timeout` applies to each individual batch read.
+    pub fn new(reader: SamplesReader, timeout: Duration) -> Result<Self> {
+        let head_index = reader.get_runtime_info()?.headIndex;
+        Ok(Self {
+            reader: Arc::new(Mutex::new(reader)),
+            last_index: AtomicU64::new(head_index),
+            timeout,
+        })
+    }
+
+    /// Index of the last sample returned so far.
+    pub fn last_index(&self) -> u64 {
+        self.last_index.load(Ordering::Acquire)
+    }
+
+    /// Waits for the next `count` samples and returns a copy of them.
+    ///
+    /// If the reader fell behind and the samples were already overwritten,
+    /// `Error::OutOfRangeTooLate` is returned and the read index is resynchronized to the current
+    /// head of the flow.
+    pub async fn next_samples(&self, count: usize) -> Result<OwnedSamplesData> {
+        let index = self.last_index() + count as u64;
+        let reader = self.reader.clone();
+        let timeout = self.timeout;
+        let outcome = tokio::task::spawn_blocking(move || {
+            let reader = reader
+                .lock()
+                .map_err(|_| Error::Other("Samples reader mutex poisoned.".to_owned()))?;
+            match reader.get_samples(index, count, timeout) {
+                Ok(samples) => Ok(Ok(samples.to_owned())),
+                Err(Error::OutOfRangeTooLate) => Ok(Err(reader.get_runtime_info()?.headIndex)),
+                Err(err) => Err(err),
+            }
+        })
+        .await
+        .map_err(|err| Error::Other(format!("Blocking samples read failed: {err}")))??;
+
+        // The index is only updated once the read completed, so a cancelled future leaves the
+        // reader state untouched.
+        match outcome {
+            Ok(samples) => {
+                self.last_index.store(index, Ordering::Release);
+                Ok(samples)
+            }
+            Err(head_index) => {
+                self.last_index.store(head_index, Ordering::Release);
+                Err(Error::OutOfRangeTooLate)
+            }
+        }
+    }
+}
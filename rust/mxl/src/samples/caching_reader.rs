@@ -0,0 +1,78 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{FlowConfigInfo, Result, SamplesData, SamplesReader, Timeout};
+
+/// A `SamplesReader` which fetches the flow configuration once, at construction, so that
+/// monitoring loops polling the head index only pay for the runtime info on every tick.
+///
+/// The configuration of a flow does not change while it exists, so the cached one only goes stale
+/// if the flow is deleted and created again with the same id, see `refresh_config`.
+pub struct CachingSamplesReader {
+    reader: SamplesReader,
+    config: FlowConfigInfo,
+    config_fetches: u64,
+}
+
+impl CachingSamplesReader {
+    pub fn new(reader: SamplesReader) -> Result<Self> {
+        let config = reader.get_config_info()?;
+        Ok(Self {
+            reader,
+            config,
+            config_fetches: 1,
+        })
+    }
+
+    /// The cached configuration of the flow.
+    pub fn config(&self) -> &FlowConfigInfo {
+        &self.config
+    }
+
+    /// Fetches the configuration of the flow again.
+    pub fn refresh_config(&mut self) -> Result<&FlowConfigInfo> {
+        self.config = self.reader.get_config_info()?;
+        self.config_fetches += 1;
+        Ok(&self.config)
+    }
+
+    /// How many times the configuration was fetched from MXL: once at construction, and once per
+    /// `refresh_config` call. Every other method only queries the runtime info.
+    pub fn config_fetches(&self) -> u64 {
+        self.config_fetches
+    }
+
+    /// The sample rate of the flow, from the cached configuration.
+    pub fn sample_rate(&self) -> Result<mxl_sys::Rational> {
+        self.config.common().sample_rate()
+    }
+
+    /// Queries the runtime info only.
+    pub fn get_runtime_info(&self) -> Result<mxl_sys::FlowRuntimeInfo> {
+        self.reader.get_runtime_info()
+    }
+
+    /// Queries the runtime info only, see `FlowReader::head_index`.
+    pub fn head_index(&self) -> Result<u64> {
+        self.reader.head_index()
+    }
+
+    /// See `SamplesReader::get_samples`.
+    pub fn get_samples(
+        &self,
+        index: u64,
+        count: usize,
+        timeout: impl Into<Timeout>,
+    ) -> Result<SamplesData<'_>> {
+        self.reader.get_samples(index, count, timeout)
+    }
+
+    /// The underlying reader, e.g. for the methods not forwarded here.
+    pub fn reader(&self) -> &SamplesReader {
+        &self.reader
+    }
+
+    pub fn into_inner(self) -> SamplesReader {
+        self.reader
+    }
+}
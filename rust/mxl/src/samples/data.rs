@@ -1,19 +1,53 @@
 // SPDX-FileCopyrightText: 2025 2025 Contributors to the Media eXchange Layer project.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::marker::PhantomData;
+use std::{borrow::Cow, marker::PhantomData};
 
 use crate::Error;
 
+/// Format of the samples of a continuous flow.
+///
+/// MXL only accepts audio flows with a `bit_depth` of 32 or 64, carrying IEEE floats. Integer PCM
+/// cannot be declared in a flow definition, so there are no integer variants (yet).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleFormat {
+    Float32,
+    Float64,
+}
+
+impl SampleFormat {
+    /// Size of a single sample in bytes.
+    pub fn sample_size(&self) -> usize {
+        match self {
+            SampleFormat::Float32 => 4,
+            SampleFormat::Float64 => 8,
+        }
+    }
+
+    pub(crate) fn from_sample_size(size: usize) -> crate::Result<Self> {
+        match size {
+            4 => Ok(SampleFormat::Float32),
+            8 => Ok(SampleFormat::Float64),
+            _ => Err(Error::Other(format!("Unsupported sample size {size}."))),
+        }
+    }
+}
+
 pub struct SamplesData<'a> {
     buffer_slice: mxl_sys::WrappedMultiBufferSlice,
+    /// Number of samples in the ring buffer of each channel.
+    buffer_length: usize,
     phantom: PhantomData<&'a ()>,
 }
 
 impl<'a> SamplesData<'a> {
-    pub(crate) fn new(buffer_slice: mxl_sys::WrappedMultiBufferSlice) -> Self {
+    pub(crate) fn new(
+        buffer_slice: mxl_sys::WrappedMultiBufferSlice,
+        buffer_length: usize,
+    ) -> Self {
         Self {
             buffer_slice,
+            buffer_length,
             phantom: Default::default(),
         }
     }
@@ -22,10 +56,33 @@ impl<'a> SamplesData<'a> {
         self.buffer_slice.count
     }
 
+    /// The format of the samples, as declared by the `bit_depth` of the flow.
+    pub fn sample_format(&self) -> crate::Result<SampleFormat> {
+        // The channel ring buffers are laid out back to back, so the stride between them is the
+        // ring buffer length in bytes.
+        if self.buffer_length == 0 {
+            return Err(Error::Other("Invalid samples buffer length.".to_owned()));
+        }
+        SampleFormat::from_sample_size(self.buffer_slice.stride / self.buffer_length)
+    }
+
+    fn check_sample_format(&self, expected: SampleFormat) -> crate::Result<()> {
+        if self.sample_format()? != expected {
+            return Err(Error::InvalidArg);
+        }
+        Ok(())
+    }
+
     pub fn channel_data(&self, channel: usize) -> crate::Result<(&[u8], &[u8])> {
         if channel >= self.buffer_slice.count {
             return Err(Error::InvalidArg);
         }
+        // The fragment sizes are those of a single channel, the channel ring buffers following each
+        // other `stride` bytes apart.
+        debug_assert!(
+            self.buffer_slice.base.fragments[0].size + self.buffer_slice.base.fragments[1].size
+                <= self.buffer_slice.stride
+        );
         unsafe {
             let ptr_1 = (self.buffer_slice.base.fragments[0].pointer as *const u8)
                 .add(self.buffer_slice.stride * channel);
@@ -40,11 +97,146 @@ impl<'a> SamplesData<'a> {
         }
     }
 
+    /// Iterates over the data of all the channels in order, see `channel_data`.
+    pub fn channels(&self) -> impl Iterator<Item = (&[u8], &[u8])> {
+        // The following unwrap is safe because the channel index always stays in the valid range.
+        (0..self.num_of_channels()).map(|channel| self.channel_data(channel).unwrap())
+    }
+
+    /// Returns the data of the given channel as a single slice, hiding the ring buffer wrap.
+    ///
+    /// When the requested range does not wrap around the end of the ring, the first fragment is
+    /// returned borrowed and no copy is made. When it does wrap, both fragments are copied into a
+    /// newly allocated buffer. Real-time users that must not allocate can check whether the second
+    /// fragment returned by `channel_data` is empty and fall back to handling the two slices.
+    pub fn channel_contiguous(&self, channel: usize) -> crate::Result<Cow<'_, [u8]>> {
+        let (data_1, data_2) = self.channel_data(channel)?;
+        if data_2.is_empty() {
+            Ok(Cow::Borrowed(data_1))
+        } else {
+            Ok(Cow::Owned([data_1, data_2].concat()))
+        }
+    }
+
+    /// Same as `channel_data`, but interprets the samples as `f32`. Fails with `Error::InvalidArg`
+    /// if the flow does not carry `SampleFormat::Float32` samples, or if the fragments are not
+    /// 4-byte aligned or their sizes are not multiples of 4 bytes.
+    pub fn channel_data_f32(&self, channel: usize) -> crate::Result<(&[f32], &[f32])> {
+        self.check_sample_format(SampleFormat::Float32)?;
+        let (data_1, data_2) = self.channel_data(channel)?;
+        Ok((bytes_as_f32(data_1)?, bytes_as_f32(data_2)?))
+    }
+
+    /// Same as `channel_data_f32`, for flows carrying `SampleFormat::Float64` samples.
+    pub fn channel_data_f64(&self, channel: usize) -> crate::Result<(&[f64], &[f64])> {
+        self.check_sample_format(SampleFormat::Float64)?;
+        let (data_1, data_2) = self.channel_data(channel)?;
+        Ok((bytes_as_f64(data_1)?, bytes_as_f64(data_2)?))
+    }
+
+    /// Peak absolute value of the `f32` samples of the given channel, across both ring buffer
+    /// fragments, 0 if there are no samples. Fails like `channel_data_f32`.
+    pub fn channel_peak(&self, channel: usize) -> crate::Result<f32> {
+        let (data_1, data_2) = self.channel_data_f32(channel)?;
+        Ok(data_1
+            .iter()
+            .chain(data_2.iter())
+            .fold(0.0, |peak: f32, sample| peak.max(sample.abs())))
+    }
+
+    /// Root mean square of the `f32` samples of the given channel, across both ring buffer
+    /// fragments, 0 if there are no samples. Fails like `channel_data_f32`.
+    pub fn channel_rms(&self, channel: usize) -> crate::Result<f32> {
+        let (data_1, data_2) = self.channel_data_f32(channel)?;
+        let count = data_1.len() + data_2.len();
+        if count == 0 {
+            return Ok(0.0);
+        }
+        // Accumulated in f64, so that long batches do not lose precision.
+        let sum_of_squares: f64 = data_1
+            .iter()
+            .chain(data_2.iter())
+            .map(|sample| f64::from(*sample) * f64::from(*sample))
+            .sum();
+        Ok((sum_of_squares / count as f64).sqrt() as f32)
+    }
+
+    /// Copies the `f32` samples of all the channels into a single interleaved buffer (for two
+    /// channels: L, R, L, R, ...), stitching together both ring buffer fragments of each channel.
+    ///
+    /// Fails with `Error::InvalidArg` if the channels do not have the same number of samples or if
+    /// the data cannot be accessed as `f32`, see `channel_data_f32`.
+    pub fn to_interleaved_f32(&self) -> crate::Result<Vec<f32>> {
+        let channels = (0..self.num_of_channels())
+            .map(|channel| self.channel_data_f32(channel))
+            .collect::<crate::Result<Vec<_>>>()?;
+        let Some(((first_1, first_2), _)) = channels.split_first() else {
+            return Ok(Vec::new());
+        };
+        let samples_per_channel = first_1.len() + first_2.len();
+        if channels
+            .iter()
+            .any(|(data_1, data_2)| data_1.len() + data_2.len() != samples_per_channel)
+        {
+            return Err(Error::InvalidArg);
+        }
+
+        let mut interleaved = vec![0.0; samples_per_channel * channels.len()];
+        for (channel, (data_1, data_2)) in channels.iter().enumerate() {
+            for (sample_index, sample) in data_1.iter().chain(data_2.iter()).enumerate() {
+                interleaved[sample_index * channels.len() + channel] = *sample;
+            }
+        }
+        Ok(interleaved)
+    }
+
     pub fn to_owned(&self) -> OwnedSamplesData {
         self.into()
     }
 }
 
+pub(crate) fn bytes_as_f32(bytes: &[u8]) -> crate::Result<&[f32]> {
+    // SAFETY
+    // Any bit pattern is a valid f32, and `align_to` only returns the properly aligned middle part.
+    let (prefix, samples, suffix) = unsafe { bytes.align_to::<f32>() };
+    if !prefix.is_empty() || !suffix.is_empty() {
+        return Err(Error::InvalidArg);
+    }
+    Ok(samples)
+}
+
+fn bytes_as_f64(bytes: &[u8]) -> crate::Result<&[f64]> {
+    // SAFETY
+    // Any bit pattern is a valid f64, and `align_to` only returns the properly aligned middle part.
+    let (prefix, samples, suffix) = unsafe { bytes.align_to::<f64>() };
+    if !prefix.is_empty() || !suffix.is_empty() {
+        return Err(Error::InvalidArg);
+    }
+    Ok(samples)
+}
+
+pub(crate) fn bytes_as_f32_mut(bytes: &mut [u8]) -> crate::Result<&mut [f32]> {
+    // SAFETY
+    // Any bit pattern is a valid f32, and `align_to_mut` only returns the properly aligned middle
+    // part.
+    let (prefix, samples, suffix) = unsafe { bytes.align_to_mut::<f32>() };
+    if !prefix.is_empty() || !suffix.is_empty() {
+        return Err(Error::InvalidArg);
+    }
+    Ok(samples)
+}
+
+pub(crate) fn bytes_as_f64_mut(bytes: &mut [u8]) -> crate::Result<&mut [f64]> {
+    // SAFETY
+    // Any bit pattern is a valid f64, and `align_to_mut` only returns the properly aligned middle
+    // part.
+    let (prefix, samples, suffix) = unsafe { bytes.align_to_mut::<f64>() };
+    if !prefix.is_empty() || !suffix.is_empty() {
+        return Err(Error::InvalidArg);
+    }
+    Ok(samples)
+}
+
 impl<'a> AsRef<SamplesData<'a>> for SamplesData<'a> {
     fn as_ref(&self) -> &SamplesData<'a> {
         self
@@ -71,6 +263,25 @@ impl<'a> From<&SamplesData<'a>> for OwnedSamplesData {
     }
 }
 
+/// Iterates over the data of each of the channels.
+impl IntoIterator for OwnedSamplesData {
+    type Item = Vec<u8>;
+    type IntoIter = std::vec::IntoIter<Vec<u8>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.payload.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a OwnedSamplesData {
+    type Item = &'a Vec<u8>;
+    type IntoIter = std::slice::Iter<'a, Vec<u8>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.payload.iter()
+    }
+}
+
 impl<'a> From<SamplesData<'a>> for OwnedSamplesData {
     fn from(value: SamplesData<'a>) -> Self {
         value.as_ref().into()
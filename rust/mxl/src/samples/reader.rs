@@ -1,13 +1,17 @@
 // SPDX-FileCopyrightText: 2025 2025 Contributors to the Media eXchange Layer project.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{sync::Arc, time::Duration};
+use std::{ops::Range, sync::Arc, time::Duration};
 
 use crate::{
-    Error, Result, SamplesData,
+    Error, Result, SamplesData, Timeout, WaitConfig,
+    error::report_drop_error,
     flow::{
         FlowConfigInfo, FlowInfo,
-        reader::{get_config_info, get_flow_info, get_runtime_info},
+        reader::{
+            DEFAULT_READ_TIMEOUT, get_config_info, get_flow_info, get_runtime_info,
+            oldest_available_index, wait_for_index,
+        },
     },
     instance::InstanceContext,
 };
@@ -15,6 +19,9 @@ use crate::{
 pub struct SamplesReader {
     context: Arc<InstanceContext>,
     reader: mxl_sys::FlowReader,
+    /// Number of samples in the ring buffer of each channel, needed to interpret the samples.
+    buffer_length: usize,
+    default_timeout: Duration,
 }
 
 /// The MXL readers and writers are not thread-safe, so we do not implement `Sync` for them, but
@@ -22,8 +29,17 @@ pub struct SamplesReader {
 unsafe impl Send for SamplesReader {}
 
 impl SamplesReader {
-    pub(crate) fn new(context: Arc<InstanceContext>, reader: mxl_sys::FlowReader) -> Self {
-        Self { context, reader }
+    pub(crate) fn new(
+        context: Arc<InstanceContext>,
+        reader: mxl_sys::FlowReader,
+        buffer_length: usize,
+    ) -> Self {
+        Self {
+            context,
+            reader,
+            buffer_length,
+            default_timeout: DEFAULT_READ_TIMEOUT,
+        }
     }
 
     pub fn destroy(mut self) -> Result<()> {
@@ -44,24 +60,72 @@ impl SamplesReader {
         get_runtime_info(&self.context, self.reader)
     }
 
+    /// See `FlowReader::head_index`.
+    pub fn head_index(&self) -> Result<u64> {
+        Ok(self.get_runtime_info()?.headIndex)
+    }
+
+    /// The indices of the samples which can be read right now, from the oldest one still in the
+    /// readable half of the ring buffer (see `FlowReader::oldest_available_index`) up to and
+    /// including the head index, so that seeks can be clamped to it.
+    ///
+    /// `get_samples(index, count, ..)` reads the `count` samples ending at `index`, so it only
+    /// succeeds if all of them are in the range, i.e. if `index >= range.start + count - 1` and
+    /// `index < range.end`. Reading outside of it fails with `Error::OutOfRangeTooLate` or
+    /// `Error::OutOfRangeTooEarly`. The writer keeps moving the range forward, so a reader seeking
+    /// to its start should leave some margin.
+    pub fn available_range(&self) -> Result<Range<u64>> {
+        let info = self.get_info()?;
+        let head_index = info.runtime.head_index();
+        Ok(oldest_available_index(&info)?..head_index.saturating_add(1))
+    }
+
+    /// Sets the timeout used by `get_samples_default`, 5 seconds if never set. Reads running out
+    /// of it fail with `Error::Timeout`.
+    pub fn set_default_timeout(&mut self, timeout: Duration) {
+        self.default_timeout = timeout;
+    }
+
+    pub fn default_timeout(&self) -> Duration {
+        self.default_timeout
+    }
+
+    /// Reads `count` samples of each channel, up to and including `index`, waiting for them as
+    /// long as `timeout` says, see `Timeout`.
+    ///
+    /// As for `GrainReader::get_complete_grain`, samples still missing when `timeout` elapses are
+    /// reported as `Error::Timeout` instead of the `Error::OutOfRangeTooEarly` of MXL, which is
+    /// only returned with `Timeout::Immediate`.
     pub fn get_samples(
         &self,
         index: u64,
         count: usize,
-        timeout: Duration,
+        timeout: impl Into<Timeout>,
     ) -> Result<SamplesData<'_>> {
-        let timeout_ns = timeout.as_nanos() as u64;
+        let timeout = timeout.into();
         let mut buffer_slice: mxl_sys::WrappedMultiBufferSlice = unsafe { std::mem::zeroed() };
-        unsafe {
-            Error::from_status(self.context.api.flow_reader_get_samples(
+        let status = unsafe {
+            self.context.api.flow_reader_get_samples(
                 self.reader,
                 index,
                 count,
-                timeout_ns,
+                timeout.as_ffi_nanos(),
                 &mut buffer_slice,
-            ))?;
+            )
+        };
+        match Error::from_status(status) {
+            Ok(()) => {}
+            Err(Error::OutOfRangeTooEarly) if timeout != Timeout::Immediate => {
+                return Err(Error::Timeout);
+            }
+            Err(error) => return Err(error),
         }
-        Ok(SamplesData::new(buffer_slice))
+        Ok(SamplesData::new(buffer_slice, self.buffer_length))
+    }
+
+    /// Same as `get_samples`, using the timeout set with `set_default_timeout`.
+    pub fn get_samples_default(&self, index: u64, count: usize) -> Result<SamplesData<'_>> {
+        self.get_samples(index, count, self.default_timeout)
     }
 
     pub fn get_samples_non_blocking(&self, index: u64, count: usize) -> Result<SamplesData<'_>> {
@@ -74,7 +138,76 @@ impl SamplesReader {
                 &mut buffer_slice,
             ))?;
         }
-        Ok(SamplesData::new(buffer_slice))
+        Ok(SamplesData::new(buffer_slice, self.buffer_length))
+    }
+
+    /// Same as `get_samples_non_blocking`, but returns `Ok(None)` instead of
+    /// `Error::OutOfRangeTooEarly` if the writer did not reach `index` yet, so that only genuine
+    /// failures are errors.
+    pub fn try_get_samples(&self, index: u64, count: usize) -> Result<Option<SamplesData<'_>>> {
+        match self.get_samples_non_blocking(index, count) {
+            Ok(samples) => Ok(Some(samples)),
+            Err(Error::OutOfRangeTooEarly) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// See `FlowReader::wait_for_index`.
+    pub fn wait_for_index(&self, index: u64, timeout: Duration) -> Result<()> {
+        wait_for_index(&self.context, self.reader, index, timeout, None)
+    }
+
+    /// See `FlowReader::wait_for_index_with`.
+    pub fn wait_for_index_with(
+        &self,
+        index: u64,
+        timeout: Duration,
+        wait_config: &WaitConfig,
+    ) -> Result<()> {
+        wait_for_index(
+            &self.context,
+            self.reader,
+            index,
+            timeout,
+            Some(wait_config),
+        )
+    }
+
+    /// Like `get_samples`, but instead of relying on the library timeout, waits for the flow head
+    /// to reach `index` (the last sample of the requested range) first, see `wait_for_index`.
+    ///
+    /// Returns `Error::Timeout` if the head does not reach `index` before `timeout` elapses, and
+    /// `Error::OutOfRangeTooLate` if the start of the range has already left the ring buffer.
+    pub fn get_samples_waiting(
+        &self,
+        index: u64,
+        count: usize,
+        timeout: Duration,
+    ) -> Result<SamplesData<'_>> {
+        self.wait_for_index(index, timeout)?;
+        self.get_samples_non_blocking(index, count)
+    }
+
+    /// Same as `get_samples`, copying the samples of each channel into the matching vector of `out`,
+    /// both ring buffer fragments one after the other. `out` is resized to the number of channels
+    /// and the previous content of the channel vectors is discarded, but their allocations are
+    /// reused if large enough, so that reading batches of the same size in a loop does not
+    /// allocate after the first read.
+    pub fn read_into(
+        &self,
+        index: u64,
+        count: usize,
+        timeout: Duration,
+        out: &mut Vec<Vec<u8>>,
+    ) -> Result<()> {
+        let samples = self.get_samples(index, count, timeout)?;
+        out.resize_with(samples.num_of_channels(), Vec::new);
+        for (buf, (data_1, data_2)) in out.iter_mut().zip(samples.channels()) {
+            buf.clear();
+            buf.extend_from_slice(data_1);
+            buf.extend_from_slice(data_2);
+        }
+        Ok(())
     }
 
     fn destroy_inner(&mut self) -> Result<()> {
@@ -99,6 +232,7 @@ impl Drop for SamplesReader {
             && let Err(err) = self.destroy_inner()
         {
             tracing::error!("Failed to release MXL flow reader (continuous): {:?}", err);
+            report_drop_error(&err);
         }
     }
 }
@@ -0,0 +1,96 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use crate::{Error, OwnedSamplesData, Result, SamplesReader, WaitConfig};
+
+/// Reads a continuous flow as a stream of consecutive batches of the same size, keeping track of
+/// the read position.
+///
+/// If the reader falls so far behind the writer that the next batch already left the ring buffer,
+/// the stream skips ahead to the batch ending at the current head instead of failing or returning
+/// overwritten samples. The number of samples lost this way is reported by `skipped_samples`.
+pub struct SamplesStreamReader {
+    reader: SamplesReader,
+    batch_size: usize,
+    /// Index of the last sample of the next batch, as MXL addresses batches by their last sample.
+    next_index: u64,
+    skipped_samples: u64,
+    wait_config: Option<WaitConfig>,
+}
+
+impl SamplesStreamReader {
+    /// Creates a stream starting with the first batch written after the current head of the flow.
+    pub fn new(reader: SamplesReader, batch_size: usize) -> Result<Self> {
+        if batch_size == 0 {
+            return Err(Error::InvalidArg);
+        }
+        let head_index = reader.head_index()?;
+        Ok(Self {
+            reader,
+            batch_size,
+            next_index: head_index + batch_size as u64,
+            skipped_samples: 0,
+            wait_config: None,
+        })
+    }
+
+    /// Index of the last sample of the batch returned by the next call to `next_batch`.
+    pub fn next_index(&self) -> u64 {
+        self.next_index
+    }
+
+    /// Total number of samples skipped so far because the reader fell behind the writer.
+    pub fn skipped_samples(&self) -> u64 {
+        self.skipped_samples
+    }
+
+    /// Sets how `next_batch` polls for the next batch, see `WaitConfig`. By default, it sleeps for
+    /// the time needed to produce the missing samples.
+    pub fn set_wait_config(&mut self, wait_config: WaitConfig) {
+        self.wait_config = Some(wait_config);
+    }
+
+    /// Waits for the next batch to be written, at most `timeout`, and returns a copy of it.
+    pub fn next_batch(&mut self, timeout: Duration) -> Result<OwnedSamplesData> {
+        loop {
+            let waited = match &self.wait_config {
+                Some(wait_config) => {
+                    self.reader
+                        .wait_for_index_with(self.next_index, timeout, wait_config)
+                }
+                None => self.reader.wait_for_index(self.next_index, timeout),
+            };
+            let result = waited.and_then(|()| {
+                self.reader
+                    .get_samples_non_blocking(self.next_index, self.batch_size)
+                    .map(|samples| samples.to_owned())
+            });
+            match result {
+                Ok(samples) => {
+                    self.next_index += self.batch_size as u64;
+                    return Ok(samples);
+                }
+                Err(Error::OutOfRangeTooLate) => self.resync()?,
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    pub fn into_inner(self) -> SamplesReader {
+        self.reader
+    }
+
+    fn resync(&mut self) -> Result<()> {
+        let head_index = self.reader.head_index()?;
+        let skipped = head_index.saturating_sub(self.next_index);
+        tracing::warn!(
+            "Samples reader fell behind, skipping {skipped} samples to resynchronize at index \
+             {head_index}."
+        );
+        self.skipped_samples += skipped;
+        self.next_index = head_index;
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    io::{BufWriter, Write},
+    path::Path,
+    time::Duration,
+};
+
+use crate::{Error, Result, SamplesReader};
+
+/// `WAVE_FORMAT_IEEE_FLOAT`, MXL samples are always floats.
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+
+impl SamplesReader {
+    /// Reads `count` samples starting at `start_index` and writes them to a WAV file at `path`,
+    /// with the sample rate and channel count of the flow. The samples are stored as 32 or 64-bit
+    /// IEEE floats, as found in the flow.
+    ///
+    /// The samples are read in one go, so `count` is limited by the ring buffer length of the flow.
+    pub fn record_wav(
+        &self,
+        path: impl AsRef<Path>,
+        start_index: u64,
+        count: usize,
+        timeout: Duration,
+    ) -> Result<()> {
+        if count == 0 {
+            return Err(Error::InvalidArg);
+        }
+        let config = self.get_config_info()?;
+        let sample_rate = config.common().sample_rate()?;
+        if sample_rate.denominator != 1 {
+            return Err(Error::Other(format!(
+                "Sample rate {}/{} cannot be stored in a WAV file.",
+                sample_rate.numerator, sample_rate.denominator
+            )));
+        }
+        let sample_rate = u32::try_from(sample_rate.numerator)
+            .map_err(|_| Error::Other("Invalid sample rate.".to_owned()))?;
+
+        // The index of a samples read is the one of the last sample.
+        let samples = self.get_samples(start_index + count as u64 - 1, count, timeout)?;
+        let sample_size = samples.sample_format()?.sample_size();
+        let channels = samples
+            .channels()
+            .map(|(data_1, data_2)| [data_1, data_2].concat())
+            .collect::<Vec<_>>();
+        let channel_count = u16::try_from(channels.len())
+            .map_err(|_| Error::Other("Too many channels for a WAV file.".to_owned()))?;
+
+        let io_error = |error: std::io::Error| {
+            Error::Other(format!(
+                "Failed to write WAV file \"{}\": {error}",
+                path.as_ref().display()
+            ))
+        };
+        let file = std::fs::File::create(path.as_ref()).map_err(io_error)?;
+        let mut file = BufWriter::new(file);
+        let data_size = u32::try_from(count * sample_size * channels.len())
+            .map_err(|_| Error::Other("Too many samples for a WAV file.".to_owned()))?;
+        write_wav_header(
+            &mut file,
+            channel_count,
+            sample_rate,
+            sample_size as u16,
+            data_size,
+        )
+        .map_err(io_error)?;
+        for sample in 0..count {
+            let range = sample * sample_size..(sample + 1) * sample_size;
+            for channel in &channels {
+                file.write_all(&channel[range.clone()]).map_err(io_error)?;
+            }
+        }
+        file.flush().map_err(io_error)
+    }
+}
+
+/// Writes the canonical 44-byte header of a WAV file holding IEEE float samples.
+fn write_wav_header(
+    writer: &mut impl Write,
+    channels: u16,
+    sample_rate: u32,
+    sample_size: u16,
+    data_size: u32,
+) -> std::io::Result<()> {
+    let block_align = channels * sample_size;
+    writer.write_all(b"RIFF")?;
+    writer.write_all(&(36 + data_size).to_le_bytes())?;
+    writer.write_all(b"WAVE")?;
+    writer.write_all(b"fmt ")?;
+    writer.write_all(&16u32.to_le_bytes())?;
+    writer.write_all(&WAVE_FORMAT_IEEE_FLOAT.to_le_bytes())?;
+    writer.write_all(&channels.to_le_bytes())?;
+    writer.write_all(&sample_rate.to_le_bytes())?;
+    writer.write_all(&(sample_rate * block_align as u32).to_le_bytes())?;
+    writer.write_all(&block_align.to_le_bytes())?;
+    writer.write_all(&(sample_size * 8).to_le_bytes())?;
+    writer.write_all(b"data")?;
+    writer.write_all(&data_size.to_le_bytes())
+}
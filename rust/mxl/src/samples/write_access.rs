@@ -5,7 +5,12 @@ use std::{marker::PhantomData, sync::Arc};
 
 use tracing::error;
 
-use crate::{Error, instance::InstanceContext};
+use crate::{
+    Error, SampleFormat,
+    error::report_drop_error,
+    instance::InstanceContext,
+    samples::data::{bytes_as_f32_mut, bytes_as_f64_mut},
+};
 
 /// RAII samples writing session
 ///
@@ -17,6 +22,8 @@ pub struct SamplesWriteAccess<'a> {
     context: Arc<InstanceContext>,
     writer: mxl_sys::FlowWriter,
     buffer_slice: mxl_sys::MutableWrappedMultiBufferSlice,
+    /// Number of samples in the ring buffer of each channel.
+    buffer_length: usize,
     /// Serves as a flag to know whether to cancel the samples on drop.
     committed_or_canceled: bool,
     phantom: PhantomData<&'a ()>,
@@ -27,16 +34,25 @@ impl<'a> SamplesWriteAccess<'a> {
         context: Arc<InstanceContext>,
         writer: mxl_sys::FlowWriter,
         buffer_slice: mxl_sys::MutableWrappedMultiBufferSlice,
+        buffer_length: usize,
     ) -> Self {
         Self {
             context,
             writer,
             buffer_slice,
+            buffer_length,
             committed_or_canceled: false,
             phantom: PhantomData,
         }
     }
 
+    /// Same as `GrainWriteAccess::flush`, for the samples written so far.
+    pub fn flush(&self) -> crate::Result<()> {
+        std::sync::atomic::fence(std::sync::atomic::Ordering::Release);
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "trace-ffi", tracing::instrument(level = "trace", skip_all))]
     pub fn commit(mut self) -> crate::Result<()> {
         self.committed_or_canceled = true;
 
@@ -57,17 +73,36 @@ impl<'a> SamplesWriteAccess<'a> {
         self.buffer_slice.count
     }
 
+    /// Same as `SamplesData::sample_format`.
+    pub fn sample_format(&self) -> crate::Result<SampleFormat> {
+        if self.buffer_length == 0 {
+            return Err(Error::Other("Invalid samples buffer length.".to_owned()));
+        }
+        SampleFormat::from_sample_size(self.buffer_slice.stride / self.buffer_length)
+    }
+
+    fn check_sample_format(&self, expected: SampleFormat) -> crate::Result<()> {
+        if self.sample_format()? != expected {
+            return Err(Error::InvalidArg);
+        }
+        Ok(())
+    }
+
     /// Provides direct access to buffer of the given channel. The access is split into two slices
     /// to cover cases when the ring is not continuous.
     ///
-    /// Currently, we provide just raw bytes access. Probably we should provide some sample-based
-    /// access and some index-based access (where we hide the complexity of 2 slices) as well?
-    ///
-    /// Samples are f32?
+    /// This is raw bytes access, usable for any sample format. See `channel_data_f32_mut` for
+    /// floating point samples.
     pub fn channel_data_mut(&mut self, channel: usize) -> crate::Result<(&mut [u8], &mut [u8])> {
         if channel >= self.buffer_slice.count {
             return Err(Error::InvalidArg);
         }
+        // The fragment sizes are those of a single channel, the channel ring buffers following each
+        // other `stride` bytes apart.
+        debug_assert!(
+            self.buffer_slice.base.fragments[0].size + self.buffer_slice.base.fragments[1].size
+                <= self.buffer_slice.stride
+        );
         unsafe {
             let ptr_1 = (self.buffer_slice.base.fragments[0].pointer as *mut u8)
                 .add(self.buffer_slice.stride * channel);
@@ -81,6 +116,59 @@ impl<'a> SamplesWriteAccess<'a> {
             ))
         }
     }
+
+    /// Same as `channel_data_mut`, but interprets the samples as `f32`. Fails with
+    /// `Error::InvalidArg` if the flow does not carry `SampleFormat::Float32` samples, or if the
+    /// fragments are not 4-byte aligned or their sizes are not multiples of 4 bytes.
+    pub fn channel_data_f32_mut(
+        &mut self,
+        channel: usize,
+    ) -> crate::Result<(&mut [f32], &mut [f32])> {
+        self.check_sample_format(SampleFormat::Float32)?;
+        let (data_1, data_2) = self.channel_data_mut(channel)?;
+        Ok((bytes_as_f32_mut(data_1)?, bytes_as_f32_mut(data_2)?))
+    }
+
+    /// Same as `channel_data_f32_mut`, for flows carrying `SampleFormat::Float64` samples.
+    pub fn channel_data_f64_mut(
+        &mut self,
+        channel: usize,
+    ) -> crate::Result<(&mut [f64], &mut [f64])> {
+        self.check_sample_format(SampleFormat::Float64)?;
+        let (data_1, data_2) = self.channel_data_mut(channel)?;
+        Ok((bytes_as_f64_mut(data_1)?, bytes_as_f64_mut(data_2)?))
+    }
+
+    /// Number of samples per channel in the batch, across both ring buffer fragments, whatever the
+    /// sample format of the flow.
+    pub fn sample_slice_len(&self) -> crate::Result<usize> {
+        Ok(
+            (self.buffer_slice.base.fragments[0].size + self.buffer_slice.base.fragments[1].size)
+                / self.sample_format()?.sample_size(),
+        )
+    }
+
+    /// Sets the `f32` sample at `sample_index` inside the batch of the given channel, picking the
+    /// right ring buffer fragment. Fails with `Error::InvalidArg` if the channel or the index is
+    /// out of range or if the flow does not carry `SampleFormat::Float32` samples, see also
+    /// `channel_data_f32_mut`.
+    pub fn set_sample(
+        &mut self,
+        channel: usize,
+        sample_index: usize,
+        value: f32,
+    ) -> crate::Result<()> {
+        let (data_1, data_2) = self.channel_data_f32_mut(channel)?;
+        let sample = if sample_index < data_1.len() {
+            &mut data_1[sample_index]
+        } else {
+            data_2
+                .get_mut(sample_index - data_1.len())
+                .ok_or(Error::InvalidArg)?
+        };
+        *sample = value;
+        Ok(())
+    }
 }
 
 impl<'a> Drop for SamplesWriteAccess<'a> {
@@ -91,6 +179,7 @@ impl<'a> Drop for SamplesWriteAccess<'a> {
             }
         {
             error!("Failed to cancel grain write on drop: {:?}", error);
+            report_drop_error(&error);
         }
     }
 }
@@ -3,12 +3,18 @@
 
 use std::sync::Arc;
 
-use crate::{Error, Result, SamplesWriteAccess, instance::InstanceContext};
+use crate::{
+    Error, Result, SamplesWriteAccess, error::report_drop_error,
+    flow::writer::get_writer_runtime_info, instance::InstanceContext,
+};
 
 /// MXL Flow Writer for continuous flows (samples-based data like audio)
 pub struct SamplesWriter {
     context: Arc<InstanceContext>,
     writer: mxl_sys::FlowWriter,
+    id: uuid::Uuid,
+    /// Number of samples in the ring buffer of each channel, needed to interpret the samples.
+    buffer_length: usize,
 }
 
 /// The MXL readers and writers are not thread-safe, so we do not implement `Sync` for them, but
@@ -16,14 +22,39 @@ pub struct SamplesWriter {
 unsafe impl Send for SamplesWriter {}
 
 impl SamplesWriter {
-    pub(crate) fn new(context: Arc<InstanceContext>, writer: mxl_sys::FlowWriter) -> Self {
-        Self { context, writer }
+    pub(crate) fn new(
+        context: Arc<InstanceContext>,
+        writer: mxl_sys::FlowWriter,
+        id: uuid::Uuid,
+        buffer_length: usize,
+    ) -> Self {
+        Self {
+            context,
+            writer,
+            id,
+            buffer_length,
+        }
     }
 
     pub fn destroy(mut self) -> Result<()> {
         self.destroy_inner()
     }
 
+    pub fn id(&self) -> uuid::Uuid {
+        self.id
+    }
+
+    /// Runtime info of the written flow, e.g. to check through `lastReadTime` whether anybody is
+    /// reading the flow. MXL provides no such query for writers, so a temporary reader is created
+    /// for each call, which makes it too expensive to call for every batch.
+    pub fn get_runtime_info(&self) -> Result<mxl_sys::FlowRuntimeInfo> {
+        get_writer_runtime_info(&self.context, self.id)
+    }
+
+    #[cfg_attr(
+        feature = "trace-ffi",
+        tracing::instrument(level = "trace", skip(self))
+    )]
     pub fn open_samples<'a>(&'a self, index: u64, count: usize) -> Result<SamplesWriteAccess<'a>> {
         let mut buffer_slice: mxl_sys::MutableWrappedMultiBufferSlice =
             unsafe { std::mem::zeroed() };
@@ -39,9 +70,57 @@ impl SamplesWriter {
             self.context.clone(),
             self.writer,
             buffer_slice,
+            self.buffer_length,
         ))
     }
 
+    /// Drives a generative source, e.g. a tone generator: writes `count` samples, or runs forever
+    /// if `None`, in batches of `batch_size` samples starting with the sample at `start_index`.
+    /// `f` fills each batch given the index of its first sample, then the batch is committed and
+    /// the call sleeps until the time of the last sample of the next batch.
+    ///
+    /// The last batch is shortened if `count` is not a multiple of `batch_size`.
+    pub fn run_source(
+        &self,
+        start_index: u64,
+        rate: &mxl_sys::Rational,
+        batch_size: usize,
+        count: Option<u64>,
+        mut f: impl FnMut(u64, &mut SamplesWriteAccess),
+    ) -> Result<()> {
+        if batch_size == 0 {
+            return Err(Error::InvalidArg);
+        }
+        let mut remaining = count;
+        let mut index = start_index;
+        while remaining != Some(0) {
+            let batch_length = remaining.map_or(batch_size as u64, |remaining| {
+                remaining.min(batch_size as u64)
+            });
+            // MXL addresses a batch by the index of its last sample.
+            let mut samples_write_access =
+                self.open_samples(index + batch_length - 1, batch_length as usize)?;
+            f(index, &mut samples_write_access);
+            samples_write_access.commit()?;
+
+            index += batch_length;
+            remaining = remaining.map(|remaining| remaining - batch_length);
+            let sleep_ns = unsafe {
+                self.context
+                    .api
+                    .get_ns_until_index(index + batch_size as u64 - 1, rate)
+            };
+            if sleep_ns == u64::MAX {
+                return Err(Error::Other(format!(
+                    "Failed to get duration until index, invalid rate {}/{}.",
+                    rate.numerator, rate.denominator
+                )));
+            }
+            unsafe { self.context.api.sleep_for_ns(sleep_ns) };
+        }
+        Ok(())
+    }
+
     fn destroy_inner(&mut self) -> Result<()> {
         if self.writer.is_null() {
             return Err(Error::InvalidArg);
@@ -64,6 +143,7 @@ impl Drop for SamplesWriter {
             && let Err(err) = self.destroy_inner()
         {
             tracing::error!("Failed to release MXL flow writer (continuous): {:?}", err);
+            report_drop_error(&err);
         }
     }
 }
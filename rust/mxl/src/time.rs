@@ -0,0 +1,175 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    fmt,
+    time::{Duration, SystemTime},
+};
+
+use crate::{Error, RationalExt, Result};
+
+/// A point in time on the MXL clock.
+///
+/// MXL expresses time as TAI nanoseconds since the SMPTE ST 2059-1 (PTP) epoch. Wrapping the raw
+/// value in a dedicated type prevents accidentally mixing timestamps with grain or sample indices,
+/// which are both `u64` on the FFI level.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(u64);
+
+impl Timestamp {
+    pub const fn from_nanos(nanos: u64) -> Self {
+        Self(nanos)
+    }
+
+    pub const fn as_nanos(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the duration elapsed from `earlier` to `self`, or `None` if `earlier` is later
+    /// than `self`.
+    pub fn checked_sub(&self, earlier: Timestamp) -> Option<Duration> {
+        self.0.checked_sub(earlier.0).map(Duration::from_nanos)
+    }
+
+    /// The system time matching this timestamp, given the offset of the MXL clock ahead of the
+    /// system clock, see `MxlInstance::clock_offset`.
+    pub fn to_system_time(&self, clock_offset: Duration) -> SystemTime {
+        let since_epoch = Duration::from_nanos(self.0).saturating_sub(clock_offset);
+        SystemTime::UNIX_EPOCH + since_epoch
+    }
+}
+
+impl From<u64> for Timestamp {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Timestamp> for u64 {
+    fn from(value: Timestamp) -> Self {
+        value.0
+    }
+}
+
+/// A SMPTE timecode, `HH:MM:SS:FF`, wrapping every 24 hours.
+///
+/// Integer rates use non-drop-frame timecode. The 30000/1001 and 60000/1001 rates use drop-frame
+/// timecode, which skips the frame numbers 0 and 1 (0 to 3 at 60000/1001) at the start of every
+/// minute not divisible by ten, so that the timecode stays in sync with the wall clock. Timecode is
+/// not defined for other rates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Timecode {
+    pub hours: u32,
+    pub minutes: u32,
+    pub seconds: u32,
+    pub frames: u32,
+    pub drop_frame: bool,
+}
+
+impl Timecode {
+    /// The timecode of the grain at `index`, counting the frames from the MXL epoch, see
+    /// `MxlInstance::index_to_timecode`.
+    pub fn from_index(index: u64, rate: &mxl_sys::Rational) -> Result<Self> {
+        let base = TimecodeBase::new(rate)?;
+        let mut frame_number = index % base.frames_per_day();
+        if base.dropped > 0 {
+            // Add back the frame numbers dropped so far, turning the frame count into the frame
+            // number of a non-drop-frame timecode.
+            let frames_per_ten_minutes = base.frames_per_ten_minutes();
+            let frames_per_minute = base.nominal * 60 - base.dropped;
+            let tens_of_minutes = frame_number / frames_per_ten_minutes;
+            let remainder = frame_number % frames_per_ten_minutes;
+            frame_number += base.dropped * 9 * tens_of_minutes;
+            if remainder > base.dropped {
+                frame_number += base.dropped * ((remainder - base.dropped) / frames_per_minute);
+            }
+        }
+        let seconds = frame_number / base.nominal;
+        // All the values are below 24 hours worth of frames, the casts cannot truncate.
+        Ok(Self {
+            hours: (seconds / 3600) as u32,
+            minutes: (seconds / 60 % 60) as u32,
+            seconds: (seconds % 60) as u32,
+            frames: (frame_number % base.nominal) as u32,
+            drop_frame: base.dropped > 0,
+        })
+    }
+
+    /// The index of the grain with this timecode during the first day of the MXL epoch, i.e. the
+    /// number of frames since midnight. Add a multiple of the frames per day to get to later days.
+    ///
+    /// Fails with `Error::InvalidArg` if the timecode is out of range, does not use the drop-frame
+    /// mode of `rate` or names a dropped frame number.
+    pub fn to_index(&self, rate: &mxl_sys::Rational) -> Result<u64> {
+        let base = TimecodeBase::new(rate)?;
+        let (hours, minutes, seconds, frames) = (
+            u64::from(self.hours),
+            u64::from(self.minutes),
+            u64::from(self.seconds),
+            u64::from(self.frames),
+        );
+        if self.drop_frame != (base.dropped > 0)
+            || hours >= 24
+            || minutes >= 60
+            || seconds >= 60
+            || frames >= base.nominal
+            || (seconds == 0 && minutes % 10 != 0 && frames < base.dropped)
+        {
+            return Err(Error::InvalidArg);
+        }
+        let total_minutes = hours * 60 + minutes;
+        Ok((total_minutes * 60 + seconds) * base.nominal + frames
+            - base.dropped * (total_minutes - total_minutes / 10))
+    }
+}
+
+impl fmt::Display for Timecode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = if self.drop_frame { ';' } else { ':' };
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{separator}{:02}",
+            self.hours, self.minutes, self.seconds, self.frames
+        )
+    }
+}
+
+/// Nominal frame rate and frame numbers dropped per minute of a timecode rate.
+struct TimecodeBase {
+    nominal: u64,
+    dropped: u64,
+}
+
+impl TimecodeBase {
+    fn new(rate: &mxl_sys::Rational) -> Result<Self> {
+        let reduced = rate
+            .reduce()
+            .filter(|rate| rate.numerator > 0 && rate.denominator > 0);
+        match reduced.map(|rate| (rate.numerator, rate.denominator)) {
+            Some((numerator, 1)) => Ok(Self {
+                nominal: numerator as u64,
+                dropped: 0,
+            }),
+            Some((30000, 1001)) => Ok(Self {
+                nominal: 30,
+                dropped: 2,
+            }),
+            Some((60000, 1001)) => Ok(Self {
+                nominal: 60,
+                dropped: 4,
+            }),
+            _ => Err(Error::Other(format!(
+                "Timecode is not defined for rate {}/{}.",
+                rate.numerator, rate.denominator
+            ))),
+        }
+    }
+
+    fn frames_per_ten_minutes(&self) -> u64 {
+        self.nominal * 600 - self.dropped * 9
+    }
+
+    fn frames_per_day(&self) -> u64 {
+        self.frames_per_ten_minutes() * 6 * 24
+    }
+}
@@ -0,0 +1,78 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::BTreeSet,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use uuid::Uuid;
+
+use crate::{Error, Result, instance::list_flow_ids};
+
+/// Change of the set of flows of a domain, see `MxlInstance::watch_flows`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowEvent {
+    Added(Uuid),
+    Removed(Uuid),
+}
+
+/// Handle of the thread watching the flows of a domain. The watch stops when it is dropped.
+pub struct FlowWatcher {
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl FlowWatcher {
+    pub(crate) fn spawn(
+        domain: String,
+        poll: Duration,
+        mut callback: impl FnMut(FlowEvent) + Send + 'static,
+    ) -> Result<Self> {
+        let (stop, stopped) = mpsc::channel();
+        let thread = std::thread::Builder::new()
+            .name("mxl-flow-watcher".to_owned())
+            .spawn(move || {
+                let mut known = BTreeSet::new();
+                loop {
+                    match list_flow_ids(&domain) {
+                        Ok(flow_ids) => {
+                            let current = flow_ids.into_iter().collect::<BTreeSet<_>>();
+                            for flow_id in known.difference(&current) {
+                                callback(FlowEvent::Removed(*flow_id));
+                            }
+                            for flow_id in current.difference(&known) {
+                                callback(FlowEvent::Added(*flow_id));
+                            }
+                            known = current;
+                        }
+                        Err(error) => tracing::warn!("Failed to watch flows: {error}"),
+                    }
+                    // Both a stop request and the handle being dropped end the watch.
+                    if !matches!(stopped.recv_timeout(poll), Err(RecvTimeoutError::Timeout)) {
+                        break;
+                    }
+                }
+            })
+            .map_err(|error| {
+                Error::Other(format!("Failed to spawn the flow watcher thread: {error}"))
+            })?;
+        Ok(Self {
+            stop: Some(stop),
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for FlowWatcher {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take()
+            && thread.join().is_err()
+        {
+            tracing::error!("Flow watcher thread panicked.");
+        }
+    }
+}
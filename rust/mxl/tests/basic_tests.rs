@@ -7,7 +7,11 @@
 /// change in the future. For now, feel free to just edit the path to your library.
 use std::time::Duration;
 
-use mxl::{MxlInstance, OwnedGrainData, OwnedSamplesData, config::get_mxl_so_path};
+use mxl::{
+    CachingSamplesReader, CreatedFlow, FlowConfigInfo, GrainReader, GrainWriter, MxlInstance,
+    OwnedGrainData, OwnedSamplesData, SamplesReader, SamplesWriter, Timeout,
+    config::get_mxl_so_path,
+};
 use tracing::info;
 
 static LOG_ONCE: std::sync::Once = std::sync::Once::new();
@@ -83,10 +87,95 @@ fn read_flow_def<P: AsRef<std::path::Path>>(path: P) -> String {
         .unwrap()
 }
 
+/// A flow created in a test domain of its own, along with a writer and a reader of it.
+///
+/// The fields are declared so that the domain directory is removed last.
+struct TestFlow<W, R> {
+    mxl_instance: MxlInstance,
+    config: FlowConfigInfo,
+    writer: W,
+    reader: R,
+    _domain_guard: TestDomainGuard,
+}
+
+fn video_flow(test: &str) -> TestFlow<GrainWriter, GrainReader> {
+    video_flow_with_def(test, &read_flow_def("lib/tests/data/v210_flow.json"))
+}
+
+/// Same as `video_flow`, for a variant of the video flow definition.
+fn video_flow_with_def(test: &str, flow_def: &str) -> TestFlow<GrainWriter, GrainReader> {
+    let (mxl_instance, domain_guard) = setup_test(test);
+    let CreatedFlow { writer, config, .. } =
+        mxl_instance.create_flow_writer(flow_def, None).unwrap();
+    let reader = mxl_instance
+        .create_flow_reader(config.common().id())
+        .unwrap()
+        .to_grain_reader()
+        .unwrap();
+    TestFlow {
+        writer: writer.to_grain_writer().unwrap(),
+        reader,
+        config,
+        mxl_instance,
+        _domain_guard: domain_guard,
+    }
+}
+
+fn audio_flow(test: &str) -> TestFlow<SamplesWriter, SamplesReader> {
+    audio_flow_with_def(test, &read_flow_def("lib/tests/data/audio_flow.json"))
+}
+
+/// Same as `audio_flow`, for a variant of the audio flow definition.
+fn audio_flow_with_def(test: &str, flow_def: &str) -> TestFlow<SamplesWriter, SamplesReader> {
+    let (mxl_instance, domain_guard) = setup_test(test);
+    let CreatedFlow { writer, config, .. } =
+        mxl_instance.create_flow_writer(flow_def, None).unwrap();
+    let reader = mxl_instance
+        .create_flow_reader(config.common().id())
+        .unwrap()
+        .to_samples_reader()
+        .unwrap();
+    TestFlow {
+        writer: writer.to_samples_writer().unwrap(),
+        reader,
+        config,
+        mxl_instance,
+        _domain_guard: domain_guard,
+    }
+}
+
+impl<W, R> TestFlow<W, R> {
+    /// The grain or sample index of the flow at the current time.
+    fn current_index(&self) -> u64 {
+        self.mxl_instance
+            .get_current_index(&self.config.common().grain_or_sample_rate())
+    }
+}
+
+impl TestFlow<GrainWriter, GrainReader> {
+    fn destroy(self) {
+        self.reader.destroy().unwrap();
+        self.writer.destroy().unwrap();
+        self.mxl_instance.destroy().unwrap();
+    }
+}
+
+impl TestFlow<SamplesWriter, SamplesReader> {
+    fn destroy(self) {
+        self.reader.destroy().unwrap();
+        self.writer.destroy().unwrap();
+        self.mxl_instance.destroy().unwrap();
+    }
+}
+
 #[test]
 fn basic_mxl_grain_writing_reading() {
     let (mxl_instance, _domain_guard) = setup_test("grains");
-    let (flow_writer, flow_config_info, was_created) = mxl_instance
+    let CreatedFlow {
+        writer: flow_writer,
+        config: flow_config_info,
+        was_created,
+    } = mxl_instance
         .create_flow_writer(
             read_flow_def("lib/tests/data/v210_flow.json").as_str(),
             None,
@@ -115,7 +204,11 @@ fn basic_mxl_grain_writing_reading() {
 #[test]
 fn basic_mxl_samples_writing_reading() {
     let (mxl_instance, _domain_guard) = setup_test("samples");
-    let (flow_writer, flow_config_info, was_created) = mxl_instance
+    let CreatedFlow {
+        writer: flow_writer,
+        config: flow_config_info,
+        was_created,
+    } = mxl_instance
         .create_flow_writer(
             read_flow_def("lib/tests/data/audio_flow.json").as_str(),
             None,
@@ -148,7 +241,11 @@ fn basic_mxl_samples_writing_reading() {
 fn get_flow_def() {
     let (mxl_instance, _domain_guard) = setup_test("flow_def");
     let flow_def = read_flow_def("lib/tests/data/v210_flow.json");
-    let (flow_writer, flow_info, was_created) = mxl_instance
+    let CreatedFlow {
+        writer: flow_writer,
+        config: flow_info,
+        was_created,
+    } = mxl_instance
         .create_flow_writer(flow_def.as_str(), None)
         .unwrap();
     assert!(was_created);
@@ -169,3 +266,2279 @@ fn garbage_collect_flows_succeeds() {
     mxl_instance.garbage_collect_flows().unwrap();
     mxl_instance.destroy().unwrap();
 }
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn grain_stream_collects_grains() {
+    use futures::StreamExt;
+
+    let flow = video_flow("grain_stream");
+    let start_index = flow.current_index();
+    let TestFlow {
+        mxl_instance,
+        writer: grain_writer,
+        reader: grain_reader,
+        _domain_guard,
+        ..
+    } = flow;
+    // Write the grains concurrently with the reading, so that the test does not depend on the
+    // flow history being long enough to hold all of them.
+    let writer_task = tokio::task::spawn_blocking(move || {
+        for index in start_index..start_index + 10 {
+            let grain_write_access = grain_writer.open_grain(index).unwrap();
+            let total_slices = grain_write_access.total_slices();
+            grain_write_access.commit(total_slices).unwrap();
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        grain_writer
+    });
+    let grains: Vec<_> = grain_reader
+        .into_stream(start_index, Duration::from_secs(5))
+        .take(10)
+        .collect()
+        .await;
+    assert_eq!(grains.len(), 10);
+    for grain in grains {
+        assert!(!grain.unwrap().payload.is_empty());
+    }
+    writer_task.await.unwrap().destroy().unwrap();
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn grain_iter_reads_range() {
+    let flow = video_flow("grain_iter");
+    let current_index = flow.current_index();
+    for index in current_index..current_index + 5 {
+        let grain_write_access = flow.writer.open_grain(index).unwrap();
+        let total_slices = grain_write_access.total_slices();
+        grain_write_access.commit(total_slices).unwrap();
+    }
+    let grains = flow
+        .reader
+        .grains(current_index..current_index + 5, Duration::from_secs(5))
+        .collect::<mxl::Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(grains.len(), 5);
+    for grain in grains {
+        assert!(!grain.payload.is_empty());
+    }
+    flow.destroy();
+}
+
+#[test]
+fn samples_f32_access() {
+    let flow = audio_flow("samples_f32");
+    let current_index = flow.current_index();
+    let mut samples_write_access = flow.writer.open_samples(current_index, 42).unwrap();
+    let (data_1, data_2) = samples_write_access.channel_data_f32_mut(0).unwrap();
+    assert_eq!(data_1.len() + data_2.len(), 42);
+    data_1.fill(0.5);
+    data_2.fill(0.5);
+    samples_write_access.commit().unwrap();
+    let samples_data = flow
+        .reader
+        .get_samples(current_index, 42, Duration::from_secs(5))
+        .unwrap();
+    assert_eq!(
+        samples_data.sample_format().unwrap(),
+        mxl::SampleFormat::Float32
+    );
+    let (data_1, data_2) = samples_data.channel_data_f32(0).unwrap();
+    assert!(data_1.iter().chain(data_2).all(|sample| *sample == 0.5));
+    assert!(matches!(
+        samples_data.channel_data_f64(0),
+        Err(mxl::Error::InvalidArg)
+    ));
+    flow.destroy();
+}
+
+#[test]
+fn create_flow_writer_reports_was_created() {
+    let (mxl_instance, _domain_guard) = setup_test("was_created");
+    let flow_def = read_flow_def("lib/tests/data/v210_flow.json");
+    let first = mxl_instance
+        .create_flow_writer(flow_def.as_str(), None)
+        .unwrap();
+    assert!(first.was_created);
+    let second = mxl_instance
+        .create_flow_writer(flow_def.as_str(), None)
+        .unwrap();
+    assert!(!second.was_created);
+    drop(second);
+    drop(first);
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn library_version_is_available() {
+    let (mxl_instance, _domain_guard) = setup_test("library_version");
+    let version = mxl_instance.library_version().unwrap();
+    info!("MXL library version: {version}");
+    assert_eq!(
+        version.to_string(),
+        format!("{}.{}.{}", version.major, version.minor, version.bugfix)
+    );
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn partial_grain_read_times_out() {
+    let flow = video_flow("partial_grain_timeout");
+    let current_index = flow.current_index();
+    let grain_write_access = flow.writer.open_grain(current_index).unwrap();
+    assert!(grain_write_access.total_slices() > 1);
+    grain_write_access.commit(1).unwrap();
+    let timeout = Duration::from_millis(200);
+    let start = std::time::Instant::now();
+    let result = flow.reader.get_complete_grain(current_index, timeout);
+    let elapsed = start.elapsed();
+    assert!(matches!(result, Err(mxl::Error::Timeout)));
+    assert!(elapsed >= timeout);
+    assert!(elapsed < timeout * 5);
+    flow.destroy();
+}
+
+#[test]
+fn grain_slices_committed_incrementally() {
+    let flow = video_flow("grain_commit_slices");
+    let current_index = flow.current_index();
+    let mut grain_write_access = flow.writer.open_grain(current_index).unwrap();
+    let total_slices = grain_write_access.total_slices();
+    grain_write_access.commit_slices(1).unwrap();
+    grain_write_access.commit_slices(2).unwrap();
+    assert!(matches!(
+        grain_write_access.commit_slices(1),
+        Err(mxl::Error::InvalidArg)
+    ));
+    assert!(matches!(
+        grain_write_access.commit_slices(total_slices + 1),
+        Err(mxl::Error::InvalidArg)
+    ));
+    assert!(matches!(
+        flow.reader.get_grain_status(current_index).unwrap(),
+        mxl::GrainStatus::Partial { committed: 2, .. }
+    ));
+    grain_write_access.commit(total_slices).unwrap();
+    flow.reader
+        .get_complete_grain(current_index, Duration::from_secs(5))
+        .unwrap();
+    flow.destroy();
+}
+
+#[test]
+fn grain_commit_below_committed_slices_is_rejected() {
+    let flow = video_flow("grain_commit_below_committed");
+    let index = flow.current_index();
+    let mut grain_write_access = flow.writer.open_grain(index).unwrap();
+    grain_write_access.commit_slices(4).unwrap();
+    assert!(matches!(
+        grain_write_access.commit(2),
+        Err(mxl::Error::InvalidArg)
+    ));
+    // The grain was canceled, readers still see the 4 slices committed before.
+    assert!(matches!(
+        flow.reader.get_grain_status(index).unwrap(),
+        mxl::GrainStatus::Partial { committed: 4, .. }
+    ));
+    flow.destroy();
+}
+
+#[test]
+fn grain_write_payload_checks_size() {
+    let flow = video_flow("grain_write_payload");
+    let current_index = flow.current_index();
+    let mut grain_write_access = flow.writer.open_grain(current_index).unwrap();
+    let max_size = grain_write_access.max_size() as usize;
+    assert!(matches!(
+        grain_write_access.write_payload(&vec![0; max_size + 1]),
+        Err(mxl::Error::InvalidArg)
+    ));
+    grain_write_access.fill_with(|_| 1);
+    assert_eq!(grain_write_access.write_payload(&[2, 3]).unwrap(), 2);
+    let total_slices = grain_write_access.total_slices();
+    grain_write_access.commit(total_slices).unwrap();
+    let grain_data = flow
+        .reader
+        .get_complete_grain(current_index, Duration::from_secs(5))
+        .unwrap();
+    assert_eq!(&grain_data.payload[..3], &[2, 3, 1]);
+    flow.destroy();
+}
+
+#[test]
+fn discrete_flow_info_for_v210() {
+    let flow = video_flow("discrete_flow_info");
+    let discrete_info = flow.config.discrete_info().unwrap();
+    // v210 packs 6 pixels into 16 bytes, lines are padded to 48 pixels.
+    assert_eq!(discrete_info.stride().unwrap(), 1920 / 48 * 128);
+    assert_eq!(discrete_info.slice_sizes().len(), 1);
+    assert!(discrete_info.grain_count() > 0);
+    let frame_rate = discrete_info.frame_rate().unwrap();
+    assert_eq!(
+        (frame_rate.numerator, frame_rate.denominator),
+        (30000, 1001)
+    );
+    // The frame dimensions are only known from the flow definition.
+    assert!(discrete_info.width().is_err());
+    let flow_def = mxl::flowdef::FlowDef::from_json(
+        &flow
+            .mxl_instance
+            .get_flow_def(flow.config.common().id())
+            .unwrap(),
+    )
+    .unwrap();
+    let discrete_info = flow.config.discrete_info_with_def(&flow_def).unwrap();
+    assert_eq!(discrete_info.width().unwrap(), 1920);
+    assert_eq!(discrete_info.height().unwrap(), 1080);
+    assert_eq!(discrete_info.grain_size().unwrap(), 1920 / 48 * 128 * 1080);
+    let grain_write_access = flow.writer.open_grain(flow.current_index()).unwrap();
+    assert_eq!(grain_write_access.total_slices(), 1080);
+    assert_eq!(
+        grain_write_access.max_size() as usize,
+        discrete_info.grain_size().unwrap()
+    );
+    drop(grain_write_access);
+    flow.destroy();
+}
+
+#[test]
+fn payload_placement_is_host() {
+    let flow = video_flow("payload_placement");
+    assert_eq!(
+        flow.config.common().payload_placement(),
+        (mxl::PayloadLocation::Host, None)
+    );
+    flow.destroy();
+}
+
+#[test]
+fn wait_for_index_follows_writer() {
+    let (mxl_instance, _domain_guard) = setup_test("wait_for_index");
+    let CreatedFlow {
+        writer: flow_writer,
+        config: flow_config_info,
+        was_created,
+    } = mxl_instance
+        .create_flow_writer(
+            read_flow_def("lib/tests/data/v210_flow.json").as_str(),
+            None,
+        )
+        .unwrap();
+    assert!(was_created);
+    let flow_id = flow_config_info.common().id().to_string();
+    let grain_count = flow_config_info.discrete_info().unwrap().grain_count() as u64;
+    let grain_writer = flow_writer.to_grain_writer().unwrap();
+    let flow_reader = mxl_instance.create_flow_reader(flow_id.as_str()).unwrap();
+    let rate = flow_config_info.common().grain_rate().unwrap();
+    let start_index = mxl_instance.get_current_index(&rate);
+    let target_index = start_index + 5;
+    let writer_thread = std::thread::spawn(move || {
+        for index in start_index..=target_index {
+            let grain_write_access = grain_writer.open_grain(index).unwrap();
+            let total_slices = grain_write_access.total_slices();
+            grain_write_access.commit(total_slices).unwrap();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        grain_writer
+    });
+    flow_reader
+        .wait_for_index(target_index, Duration::from_secs(5))
+        .unwrap();
+    let grain_writer = writer_thread.join().unwrap();
+    assert!(matches!(
+        flow_reader.wait_for_index(target_index - grain_count, Duration::from_secs(5)),
+        Err(mxl::Error::OutOfRangeTooLate)
+    ));
+    assert!(matches!(
+        flow_reader.wait_for_index(target_index + 1, Duration::from_millis(50)),
+        Err(mxl::Error::Timeout)
+    ));
+    drop(flow_reader);
+    grain_writer.destroy().unwrap();
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn flow_exists_without_reader() {
+    let (mxl_instance, _domain_guard) = setup_test("flow_exists");
+    let flow_def = read_flow_def("lib/tests/data/v210_flow.json");
+    let flow_id = mxl::flowdef::FlowDef::from_json(flow_def.as_str())
+        .unwrap()
+        .id
+        .to_string();
+    assert!(!mxl_instance.flow_exists(flow_id.as_str()).unwrap());
+    let created_flow = mxl_instance
+        .create_flow_writer(flow_def.as_str(), None)
+        .unwrap();
+    assert!(mxl_instance.flow_exists(flow_id.as_str()).unwrap());
+    assert!(mxl_instance.is_flow_active(flow_id.as_str()).unwrap());
+    drop(created_flow);
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn to_grain_writer_right_after_create() {
+    let (mxl_instance, _domain_guard) = setup_test("to_grain_writer");
+    let CreatedFlow {
+        writer: flow_writer,
+        was_created,
+        ..
+    } = mxl_instance
+        .create_flow_writer(
+            read_flow_def("lib/tests/data/v210_flow.json").as_str(),
+            None,
+        )
+        .unwrap();
+    assert!(was_created);
+    let grain_writer = flow_writer.to_grain_writer().unwrap();
+    grain_writer.destroy().unwrap();
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn delete_and_gc_inactive_flows() {
+    let (mxl_instance, _domain_guard) = setup_test("delete_flow");
+    let video_flow_def = read_flow_def("lib/tests/data/v210_flow.json");
+    let audio_flow_def = read_flow_def("lib/tests/data/audio_flow.json");
+    let video_flow = mxl_instance
+        .create_flow_writer(video_flow_def.as_str(), None)
+        .unwrap();
+    let video_flow_id = video_flow.config.common().id();
+    let audio_flow = mxl_instance
+        .create_flow_writer(audio_flow_def.as_str(), None)
+        .unwrap();
+    let audio_flow_id = audio_flow.config.common().id();
+    assert!(matches!(
+        mxl_instance.delete_flow(video_flow_id.to_string().as_str()),
+        Err(mxl::Error::Conflict)
+    ));
+    assert!(
+        mxl_instance
+            .gc_stale_flows(Duration::ZERO)
+            .unwrap()
+            .is_empty()
+    );
+
+    drop(video_flow);
+    mxl_instance
+        .delete_flow(video_flow_id.to_string().as_str())
+        .unwrap();
+    assert!(
+        !mxl_instance
+            .flow_exists(video_flow_id.to_string().as_str())
+            .unwrap()
+    );
+
+    drop(audio_flow);
+    assert_eq!(
+        mxl_instance.gc_stale_flows(Duration::ZERO).unwrap(),
+        vec![audio_flow_id]
+    );
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn samples_interleaved_f32() {
+    let flow = audio_flow("samples_interleaved");
+    let current_index = flow.current_index();
+    let mut samples_write_access = flow.writer.open_samples(current_index, 8).unwrap();
+    assert_eq!(samples_write_access.channels(), 2);
+    for channel in 0..2 {
+        let (data_1, data_2) = samples_write_access.channel_data_f32_mut(channel).unwrap();
+        for (i, sample) in data_1.iter_mut().chain(data_2.iter_mut()).enumerate() {
+            *sample = (channel * 1000 + i) as f32;
+        }
+    }
+    samples_write_access.commit().unwrap();
+    let samples_data = flow
+        .reader
+        .get_samples(current_index, 8, Duration::from_secs(5))
+        .unwrap();
+    let interleaved = samples_data.to_interleaved_f32().unwrap();
+    let expected: Vec<f32> = (0..8).flat_map(|i| [i as f32, (1000 + i) as f32]).collect();
+    assert_eq!(interleaved, expected);
+    flow.destroy();
+}
+
+#[test]
+fn instance_builder_validates_domain() {
+    let mxl_api = mxl::load_api(get_mxl_so_path()).unwrap();
+    let domain_guard = TestDomainGuard::new("instance_builder");
+
+    let missing_domain = format!("{}/missing", domain_guard.domain());
+    assert!(matches!(
+        MxlInstance::builder(mxl_api.clone())
+            .domain(&missing_domain)
+            .build(),
+        Err(mxl::Error::Other(_))
+    ));
+    assert!(MxlInstance::builder(mxl_api.clone()).build().is_err());
+
+    let mxl_instance = MxlInstance::builder(mxl_api)
+        .domain(domain_guard.domain())
+        .history_duration(Duration::from_millis(500))
+        .option("urn:x-mxl:option:test/v1.0", "value")
+        .build()
+        .unwrap();
+    let flow_def = read_flow_def("lib/tests/data/v210_flow.json");
+    let CreatedFlow { writer, .. } = mxl_instance.create_flow_writer(&flow_def, None).unwrap();
+    writer.to_grain_writer().unwrap().destroy().unwrap();
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn new_creating_domain_creates_nested_directory() {
+    let mxl_api = mxl::load_api(get_mxl_so_path()).unwrap();
+    let domain_guard = TestDomainGuard::new("new_creating_domain");
+    let domain = format!("{}/nested/domain", domain_guard.domain());
+    assert!(!std::path::Path::new(&domain).exists());
+
+    let mxl_instance = MxlInstance::new_creating_domain(mxl_api, &domain, "").unwrap();
+    assert!(std::path::Path::new(&domain).is_dir());
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn grain_read_batch_returns_independent_results() {
+    let flow = video_flow("grain_read_batch");
+    let current_index = flow.current_index();
+    for index in current_index..current_index + 5 {
+        let grain_write_access = flow.writer.open_grain(index).unwrap();
+        let total_slices = grain_write_access.total_slices();
+        grain_write_access.commit(total_slices).unwrap();
+    }
+    let results = flow.reader.read_batch(
+        &[current_index, current_index + 2, current_index + 4],
+        Duration::from_secs(5),
+    );
+    assert_eq!(results.len(), 3);
+    for result in results {
+        assert!(!result.unwrap().payload.is_empty());
+    }
+
+    // A grain that is not written yet fails on its own, without affecting its neighbours.
+    let results = flow.reader.read_batch(
+        &[current_index, current_index + 100, current_index + 4],
+        Duration::from_millis(10),
+    );
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+    assert!(results[2].is_ok());
+
+    flow.destroy();
+}
+
+#[test]
+fn flow_reader_clones_read_independently() {
+    let (mxl_instance, _domain_guard) = setup_test("flow_reader_clone");
+    let CreatedFlow {
+        writer: flow_writer,
+        config: flow_config_info,
+        was_created,
+    } = mxl_instance
+        .create_flow_writer(
+            read_flow_def("lib/tests/data/v210_flow.json").as_str(),
+            None,
+        )
+        .unwrap();
+    assert!(was_created);
+    let flow_id = flow_config_info.common().id();
+    let grain_writer = flow_writer.to_grain_writer().unwrap();
+    let flow_reader = mxl_instance
+        .create_flow_reader(flow_id.to_string().as_str())
+        .unwrap();
+    assert_eq!(flow_reader.flow_id(), flow_id);
+    let cloned_reader = flow_reader.try_clone().unwrap();
+    assert_eq!(cloned_reader.flow_id(), flow_id);
+
+    let rate = flow_config_info.common().grain_rate().unwrap();
+    let index = mxl_instance.get_current_index(&rate);
+    let mut grain_write_access = grain_writer.open_grain(index).unwrap();
+    let total_slices = grain_write_access.total_slices();
+    grain_write_access.fill_with(|i| (i % 251) as u8);
+    grain_write_access.commit(total_slices).unwrap();
+
+    let grain_reader = flow_reader.to_grain_reader().unwrap();
+    let cloned_grain_reader = cloned_reader.to_grain_reader().unwrap();
+    let grain = grain_reader
+        .get_complete_grain(index, Duration::from_secs(5))
+        .unwrap()
+        .to_owned();
+    // Releasing the first reader must not affect the clone.
+    grain_reader.destroy().unwrap();
+    let cloned_grain = cloned_grain_reader
+        .get_complete_grain(index, Duration::from_secs(5))
+        .unwrap()
+        .to_owned();
+    assert_eq!(grain.payload, cloned_grain.payload);
+
+    cloned_grain_reader.destroy().unwrap();
+    grain_writer.destroy().unwrap();
+    mxl_instance.destroy().unwrap();
+}
+
+fn audio_flow_def_with_bit_depth(bit_depth: u8) -> String {
+    let mut flow_def =
+        mxl::flowdef::FlowDef::from_json(&read_flow_def("lib/tests/data/audio_flow.json")).unwrap();
+    flow_def.id = uuid::Uuid::new_v4();
+    let mxl::flowdef::FlowDefDetails::Audio(audio) = &mut flow_def.details else {
+        panic!("Audio flow definition expected.");
+    };
+    audio.bit_depth = bit_depth;
+    flow_def.to_json().unwrap()
+}
+
+#[test]
+fn samples_f64_access() {
+    let flow = audio_flow_with_def("samples_f64", &audio_flow_def_with_bit_depth(64));
+    let current_index = flow.current_index();
+    let mut samples_write_access = flow.writer.open_samples(current_index, 42).unwrap();
+
+    assert_eq!(
+        samples_write_access.sample_format().unwrap(),
+        mxl::SampleFormat::Float64
+    );
+    assert_eq!(samples_write_access.sample_slice_len().unwrap(), 42);
+    assert!(matches!(
+        samples_write_access.channel_data_f32_mut(0),
+        Err(mxl::Error::InvalidArg)
+    ));
+    assert!(matches!(
+        samples_write_access.set_sample(0, 0, 1.0),
+        Err(mxl::Error::InvalidArg)
+    ));
+    let (data_1, data_2) = samples_write_access.channel_data_f64_mut(0).unwrap();
+    for (i, sample) in data_1.iter_mut().chain(data_2.iter_mut()).enumerate() {
+        *sample = i as f64 * 0.25;
+    }
+    samples_write_access.commit().unwrap();
+    let samples_data = flow
+        .reader
+        .get_samples(current_index, 42, Duration::from_secs(5))
+        .unwrap();
+    assert_eq!(
+        samples_data.sample_format().unwrap(),
+        mxl::SampleFormat::Float64
+    );
+    let (data_1, data_2) = samples_data.channel_data_f64(0).unwrap();
+    assert_eq!(data_1.len() + data_2.len(), 42);
+    assert!(
+        data_1
+            .iter()
+            .chain(data_2.iter())
+            .enumerate()
+            .all(|(i, sample)| *sample == i as f64 * 0.25)
+    );
+    assert!(matches!(
+        samples_data.channel_data_f32(0),
+        Err(mxl::Error::InvalidArg)
+    ));
+    flow.destroy();
+}
+
+#[test]
+fn integer_pcm_flow_is_rejected() {
+    let (mxl_instance, _domain_guard) = setup_test("integer_pcm");
+    assert!(
+        mxl_instance
+            .create_flow_writer(&audio_flow_def_with_bit_depth(16), None)
+            .is_err()
+    );
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn audio_flow_sdp() {
+    let flow = audio_flow("audio_flow_sdp");
+    assert_eq!(
+        flow.config.to_sdp().unwrap(),
+        "v=0\r\n\
+         o=- 0 0 IN IP4 0.0.0.0\r\n\
+         s=b3bb5be7-9fe9-4324-a5bb-4c70e1084449\r\n\
+         t=0 0\r\n\
+         m=audio 0 RTP/AVP 97\r\n\
+         a=rtpmap:97 L24/48000/2\r\n\
+         a=ptime:1\r\n\
+         a=ts-refclk:ptp=traceable\r\n\
+         a=mediaclk:direct=0\r\n"
+    );
+    flow.destroy();
+}
+
+#[test]
+fn instance_metrics_lists_flows() {
+    let flow = video_flow("instance_metrics");
+    let metrics = flow.mxl_instance.metrics().unwrap();
+    assert_eq!(metrics.len(), 1);
+    assert_eq!(metrics[0].flow_id, flow.config.common().id());
+    flow.destroy();
+}
+
+#[test]
+fn grain_read_into_reuses_buffer() {
+    let flow = video_flow("grain_read_into");
+    let current_index = flow.current_index();
+    for index in current_index..current_index + 3 {
+        let mut grain_write_access = flow.writer.open_grain(index).unwrap();
+        let total_slices = grain_write_access.total_slices();
+        grain_write_access.fill_with(|_| index as u8);
+        grain_write_access.commit(total_slices).unwrap();
+    }
+
+    let mut buf = Vec::new();
+    flow.reader
+        .read_into(current_index, Duration::from_secs(5), &mut buf)
+        .unwrap();
+    let grain_size = buf.len();
+    assert!(grain_size > 0);
+    let capacity = buf.capacity();
+    let ptr = buf.as_ptr();
+    for index in current_index + 1..current_index + 3 {
+        flow.reader
+            .read_into(index, Duration::from_secs(5), &mut buf)
+            .unwrap();
+        assert_eq!(buf.len(), grain_size);
+        assert_eq!(buf.capacity(), capacity);
+        assert_eq!(buf.as_ptr(), ptr);
+        assert!(buf.iter().all(|byte| *byte == index as u8));
+    }
+
+    flow.destroy();
+}
+
+#[test]
+fn write_grain_roundtrip() {
+    let flow = video_flow("write_grain");
+    let index = flow.current_index();
+    let grain_size = flow.writer.open_grain(index).unwrap().max_size() as usize;
+
+    assert!(matches!(
+        flow.writer.write_grain(index, &vec![0; grain_size - 1]),
+        Err(mxl::Error::InvalidArg)
+    ));
+    let payload: Vec<u8> = (0..grain_size).map(|i| (i % 253) as u8).collect();
+    flow.writer.write_grain(index, &payload).unwrap();
+    let grain = flow
+        .reader
+        .get_complete_grain(index, Duration::from_secs(5))
+        .unwrap();
+    assert_eq!(grain.payload, payload.as_slice());
+
+    flow.destroy();
+}
+
+#[test]
+fn grain_status_reports_partial_grains() {
+    let flow = video_flow("grain_status");
+    let index = flow.current_index();
+    assert!(matches!(
+        flow.reader.get_grain_status(index + 100).unwrap(),
+        mxl::GrainStatus::NotReady
+    ));
+
+    let mut grain_write_access = flow.writer.open_grain(index).unwrap();
+    let total_slices = grain_write_access.total_slices();
+    grain_write_access.commit_slices(total_slices / 2).unwrap();
+    match flow.reader.get_grain_status(index).unwrap() {
+        mxl::GrainStatus::Partial { committed, total } => {
+            assert_eq!(committed, total_slices / 2);
+            assert_eq!(total, total_slices);
+        }
+        _ => panic!("Partial grain expected."),
+    }
+
+    grain_write_access.commit(total_slices).unwrap();
+    match flow.reader.get_grain_status(index).unwrap() {
+        mxl::GrainStatus::Complete(grain) => assert!(!grain.payload.is_empty()),
+        _ => panic!("Complete grain expected."),
+    }
+
+    flow.destroy();
+}
+
+#[test]
+fn samples_set_sample_across_ring_wrap() {
+    let flow = audio_flow("samples_set_sample");
+    let current_index = flow.current_index();
+    let buffer_length = flow.config.continuous().unwrap().bufferLength as u64;
+    // The batch ends 4 samples after a ring buffer boundary, so it wraps.
+    let index = (current_index / buffer_length + 1) * buffer_length + 4;
+    let mut samples_write_access = flow.writer.open_samples(index, 10).unwrap();
+    let (data_1, data_2) = samples_write_access.channel_data_f32_mut(0).unwrap();
+    assert_eq!((data_1.len(), data_2.len()), (5, 5));
+    assert_eq!(samples_write_access.sample_slice_len().unwrap(), 10);
+    for channel in 0..samples_write_access.channels() {
+        for i in 0..10 {
+            samples_write_access
+                .set_sample(channel, i, (channel * 100 + i) as f32)
+                .unwrap();
+        }
+    }
+    assert!(matches!(
+        samples_write_access.set_sample(0, 10, 0.0),
+        Err(mxl::Error::InvalidArg)
+    ));
+    samples_write_access.commit().unwrap();
+
+    let samples_data = flow
+        .reader
+        .get_samples(index, 10, Duration::from_secs(5))
+        .unwrap();
+    let (data_1, data_2) = samples_data.channel_data_f32(1).unwrap();
+    let expected: Vec<f32> = (0..10).map(|i| (100 + i) as f32).collect();
+    assert_eq!([data_1, data_2].concat(), expected);
+    flow.destroy();
+}
+
+#[test]
+fn samples_channels_iterate_in_order() {
+    let flow = audio_flow("samples_channels");
+    let current_index = flow.current_index();
+    let mut samples_write_access = flow.writer.open_samples(current_index, 8).unwrap();
+    for channel in 0..samples_write_access.channels() {
+        let (data_1, data_2) = samples_write_access.channel_data_mut(channel).unwrap();
+        data_1.fill(channel as u8 + 1);
+        data_2.fill(channel as u8 + 1);
+    }
+    samples_write_access.commit().unwrap();
+
+    let samples_data = flow
+        .reader
+        .get_samples(current_index, 8, Duration::from_secs(5))
+        .unwrap();
+    let channels: Vec<_> = samples_data.channels().collect();
+    assert_eq!(channels.len(), samples_data.num_of_channels());
+    for (channel, (data_1, data_2)) in channels.into_iter().enumerate() {
+        assert!(
+            data_1
+                .iter()
+                .chain(data_2)
+                .all(|byte| *byte == channel as u8 + 1)
+        );
+    }
+    let owned = samples_data.to_owned();
+    assert_eq!((&owned).into_iter().count(), 2);
+    for (channel, payload) in owned.into_iter().enumerate() {
+        assert!(payload.iter().all(|byte| *byte == channel as u8 + 1));
+    }
+
+    flow.destroy();
+}
+
+#[test]
+fn try_get_past_head_returns_none() {
+    let video = video_flow("try_get_grain");
+    let index = video.current_index();
+    let grain_size = video.writer.open_grain(index).unwrap().max_size() as usize;
+    video
+        .writer
+        .write_grain(index, &vec![0; grain_size])
+        .unwrap();
+    assert!(video.reader.try_get_grain(index).unwrap().is_some());
+    assert!(video.reader.try_get_grain(index + 10).unwrap().is_none());
+    video.destroy();
+
+    let audio = audio_flow("try_get_samples");
+    let index = audio.current_index();
+    audio
+        .writer
+        .open_samples(index, 32)
+        .unwrap()
+        .commit()
+        .unwrap();
+    assert!(audio.reader.try_get_samples(index, 32).unwrap().is_some());
+    assert!(
+        audio
+            .reader
+            .try_get_samples(index + 1000, 32)
+            .unwrap()
+            .is_none()
+    );
+    audio.destroy();
+}
+
+#[test]
+fn shared_grain_writer_from_two_threads() {
+    let mut flow = video_flow("shared_grain_writer");
+    let start_index = flow.current_index();
+    let grain_size = flow.writer.open_grain(0).unwrap().max_size() as usize;
+    let shared_writer = mxl::SharedGrainWriter::new(flow.writer);
+
+    let threads: Vec<_> = (0..2u64)
+        .map(|parity| {
+            let shared_writer = shared_writer.clone();
+            std::thread::spawn(move || {
+                for index in (start_index..start_index + 8).filter(|index| index % 2 == parity) {
+                    shared_writer
+                        .write_grain(index, &vec![index as u8; grain_size])
+                        .unwrap();
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    // The head is the last written index, so finish with a grain after all the others.
+    shared_writer
+        .write_grain(start_index + 8, &vec![0; grain_size])
+        .unwrap();
+
+    for index in start_index..start_index + 8 {
+        let grain = flow
+            .reader
+            .get_complete_grain(index, Duration::from_secs(5))
+            .unwrap();
+        assert!(grain.payload.iter().all(|byte| *byte == index as u8));
+    }
+
+    flow.writer = shared_writer.try_into_inner().unwrap();
+    flow.destroy();
+}
+
+#[test]
+fn record_wav_writes_header_and_samples() {
+    let flow = audio_flow("record_wav");
+    let last_index = flow.current_index();
+    let mut samples_write_access = flow.writer.open_samples(last_index, 16).unwrap();
+    for channel in 0..2 {
+        for i in 0..16 {
+            samples_write_access
+                .set_sample(channel, i, (channel * 100 + i) as f32)
+                .unwrap();
+        }
+    }
+    samples_write_access.commit().unwrap();
+
+    let path = std::path::Path::new(flow.mxl_instance.domain()).join("segment.wav");
+    flow.reader
+        .record_wav(&path, last_index - 15, 16, Duration::from_secs(5))
+        .unwrap();
+    let wav = std::fs::read(&path).unwrap();
+    let u16_at = |offset: usize| u16::from_le_bytes(wav[offset..offset + 2].try_into().unwrap());
+    let u32_at = |offset: usize| u32::from_le_bytes(wav[offset..offset + 4].try_into().unwrap());
+    assert_eq!(&wav[0..4], b"RIFF");
+    assert_eq!(&wav[8..12], b"WAVE");
+    assert_eq!(u16_at(20), 3);
+    assert_eq!(u16_at(22), 2);
+    assert_eq!(u32_at(24), 48000);
+    assert_eq!(u16_at(34), 32);
+    assert_eq!(&wav[36..40], b"data");
+    assert_eq!(u32_at(40), 16 * 2 * 4);
+    assert_eq!(wav.len(), 44 + 16 * 2 * 4);
+    // The second sample of the second channel.
+    assert_eq!(f32::from_le_bytes(wav[56..60].try_into().unwrap()), 101.0);
+
+    flow.destroy();
+}
+
+#[test]
+fn dump_frame_writes_grain_and_sidecar() {
+    let flow = video_flow("dump_frame");
+    let index = flow.current_index();
+    let mut grain_write_access = flow.writer.open_grain(index).unwrap();
+    let grain_size = grain_write_access.max_size();
+    let total_slices = grain_write_access.total_slices();
+    grain_write_access.fill_with(|i| (i % 256) as u8);
+    grain_write_access.commit(total_slices).unwrap();
+
+    let path = std::path::Path::new(flow.mxl_instance.domain()).join("frame.v210");
+    flow.reader
+        .dump_frame(index, &path, Duration::from_secs(5))
+        .unwrap();
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), grain_size as u64);
+    let sidecar: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path.with_extension("v210.json")).unwrap())
+            .unwrap();
+    assert_eq!(sidecar["frame_width"], 1920);
+    assert_eq!(sidecar["frame_height"], 1080);
+    assert_eq!(sidecar["grain_size"], grain_size);
+
+    flow.destroy();
+}
+
+#[test]
+fn default_read_timeout_is_applied() {
+    let mut flow = video_flow("default_read_timeout");
+    let current_index = flow.current_index();
+    // A partial grain never completes, so that every read waits for the whole timeout.
+    flow.writer
+        .open_grain(current_index)
+        .unwrap()
+        .commit(1)
+        .unwrap();
+
+    let timeout = Duration::from_millis(200);
+    flow.reader.set_default_timeout(timeout);
+    assert_eq!(flow.reader.default_timeout(), timeout);
+    let start = std::time::Instant::now();
+    let result = flow.reader.get_complete_grain_default(current_index);
+    let elapsed = start.elapsed();
+    assert!(matches!(result, Err(mxl::Error::Timeout)));
+    assert!(elapsed >= timeout);
+    assert!(elapsed < timeout * 5);
+
+    // An explicit timeout still overrides the default one.
+    flow.reader.set_default_timeout(Duration::from_secs(60));
+    let start = std::time::Instant::now();
+    let result = flow.reader.get_complete_grain(current_index, timeout);
+    let elapsed = start.elapsed();
+    assert!(matches!(result, Err(mxl::Error::Timeout)));
+    assert!(elapsed < timeout * 5);
+
+    flow.destroy();
+}
+
+#[test]
+fn latest_returns_newest_complete_grain() {
+    let flow = video_flow("latest_grain");
+    let current_index = flow.current_index();
+    for index in current_index..current_index + 3 {
+        let mut grain_write_access = flow.writer.open_grain(index).unwrap();
+        let total_slices = grain_write_access.total_slices();
+        grain_write_access.fill_with(|_| (index % 256) as u8);
+        grain_write_access.commit(total_slices).unwrap();
+    }
+
+    let (index, grain) = flow.reader.latest(Duration::from_secs(5)).unwrap();
+    assert_eq!(index, current_index + 2);
+    assert!(
+        grain
+            .payload
+            .iter()
+            .all(|byte| *byte == (index % 256) as u8)
+    );
+
+    // The grain being written is skipped in favor of the previous, complete one.
+    flow.writer
+        .open_grain(current_index + 3)
+        .unwrap()
+        .commit(1)
+        .unwrap();
+    let (index, _) = flow.reader.latest(Duration::from_secs(5)).unwrap();
+    assert_eq!(index, current_index + 2);
+
+    flow.destroy();
+}
+
+#[test]
+fn flow_reader_data_format_matches_flow() {
+    let (mxl_instance, _domain_guard) = setup_test("flow_reader_data_format");
+    for (flow_def, data_format) in [
+        ("lib/tests/data/v210_flow.json", mxl::DataFormat::Video),
+        ("lib/tests/data/audio_flow.json", mxl::DataFormat::Audio),
+    ] {
+        let CreatedFlow {
+            writer: flow_writer,
+            config: flow_config_info,
+            ..
+        } = mxl_instance
+            .create_flow_writer(read_flow_def(flow_def).as_str(), None)
+            .unwrap();
+        assert_eq!(flow_config_info.common().data_format(), data_format);
+        let flow_id = flow_config_info.common().id().to_string();
+        let flow_reader = mxl_instance.create_flow_reader(flow_id.as_str()).unwrap();
+        assert_eq!(flow_reader.data_format().unwrap(), data_format);
+        drop(flow_reader);
+        drop(flow_writer);
+    }
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn samples_channels_do_not_alias() {
+    let mut flow_def =
+        mxl::flowdef::FlowDef::from_json(&read_flow_def("lib/tests/data/audio_flow.json")).unwrap();
+    let mxl::flowdef::FlowDefDetails::Audio(audio) = &mut flow_def.details else {
+        panic!("Audio flow definition expected.");
+    };
+    audio.channel_count = 3;
+    let flow = audio_flow_with_def(
+        "samples_channels_alias",
+        flow_def.to_json().unwrap().as_str(),
+    );
+    let last_index = flow.current_index();
+    let mut samples_write_access = flow.writer.open_samples(last_index, 64).unwrap();
+    for channel in 0..3 {
+        let (data_1, data_2) = samples_write_access.channel_data_f32_mut(channel).unwrap();
+        assert_eq!(data_1.len() + data_2.len(), 64);
+        data_1.fill(channel as f32);
+        data_2.fill(channel as f32);
+    }
+    samples_write_access.commit().unwrap();
+
+    let samples_data = flow
+        .reader
+        .get_samples(last_index, 64, Duration::from_secs(5))
+        .unwrap();
+    assert_eq!(samples_data.num_of_channels(), 3);
+    let (channel_0, _) = samples_data.channel_data(0).unwrap();
+    let (channel_1, _) = samples_data.channel_data(1).unwrap();
+    let channel_0_end = channel_0.as_ptr_range().end;
+    assert!(channel_0_end <= channel_1.as_ptr());
+    for channel in 0..3 {
+        let (data_1, data_2) = samples_data.channel_data_f32(channel).unwrap();
+        assert!(
+            data_1
+                .iter()
+                .chain(data_2)
+                .all(|sample| *sample == channel as f32)
+        );
+    }
+
+    flow.destroy();
+}
+
+#[test]
+fn nul_bytes_in_flow_ids_are_errors() {
+    let (mxl_instance, _domain_guard) = setup_test("nul_bytes");
+    assert!(matches!(
+        mxl_instance.create_flow_reader("bad\0id"),
+        Err(mxl::Error::NulString(_))
+    ));
+    assert!(matches!(
+        mxl_instance.get_flow_def("bad\0id"),
+        Err(mxl::Error::NulString(_))
+    ));
+    let flow_def = read_flow_def("lib/tests/data/v210_flow.json").replacen('{', "{\0", 1);
+    assert!(matches!(
+        mxl_instance.create_flow_writer(flow_def.as_str(), None),
+        Err(mxl::Error::NulString(_))
+    ));
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn committed_grain_has_no_error_flags() {
+    let flow = video_flow("grain_flags");
+    let index = flow.current_index();
+    let grain_write_access = flow.writer.open_grain(index).unwrap();
+    let total_slices = grain_write_access.total_slices();
+    grain_write_access.commit(total_slices).unwrap();
+
+    let grain = flow
+        .reader
+        .get_complete_grain(index, Duration::from_secs(5))
+        .unwrap();
+    assert!(!grain.flags().is_invalid());
+    assert_eq!(grain.flags(), mxl::GrainFlags::empty());
+    assert_eq!(grain.to_owned().flags(), grain.flags());
+
+    flow.destroy();
+}
+
+#[test]
+fn flow_config_info_debug_shows_id_and_format() {
+    let flow = video_flow("flow_config_info_debug");
+    let debug = format!("{:?}", flow.config);
+    assert!(debug.contains(&flow.config.common().id().to_string()));
+    assert!(debug.contains("Video"));
+    assert!(debug.contains("grain_count"));
+    assert!(!debug.contains("channel_count"));
+    flow.destroy();
+}
+
+#[test]
+fn run_source_writes_requested_grains() {
+    let flow = video_flow("run_source");
+    let rate = flow.config.common().grain_rate().unwrap();
+    let start_index = flow.current_index();
+    let mut written = Vec::new();
+    flow.writer
+        .run_source(start_index, &rate, Some(3), |index, payload| {
+            payload[0] = (index % 256) as u8;
+            written.push(index);
+        })
+        .unwrap();
+    assert_eq!(written, (start_index..start_index + 3).collect::<Vec<_>>());
+
+    for index in start_index..start_index + 3 {
+        let grain = flow
+            .reader
+            .get_complete_grain(index, Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(grain.payload[0], (index % 256) as u8);
+    }
+    assert_eq!(
+        flow.reader.get_runtime_info().unwrap().headIndex,
+        start_index + 2
+    );
+
+    flow.destroy();
+}
+
+#[test]
+fn samples_run_source_writes_batches() {
+    let flow = audio_flow("samples_run_source");
+    let rate = flow.config.common().sample_rate().unwrap();
+    let start_index = flow.current_index();
+    let mut batches = Vec::new();
+    flow.writer
+        .run_source(
+            start_index,
+            &rate,
+            32,
+            Some(64),
+            |first_index, samples_write_access| {
+                for channel in 0..samples_write_access.channels() {
+                    for i in 0..32 {
+                        let value =
+                            ((first_index - start_index) as usize + i + channel * 100) as f32;
+                        samples_write_access.set_sample(channel, i, value).unwrap();
+                    }
+                }
+                batches.push(first_index);
+            },
+        )
+        .unwrap();
+    assert_eq!(batches, vec![start_index, start_index + 32]);
+
+    let samples_data = flow
+        .reader
+        .get_samples(start_index + 63, 64, Duration::from_secs(5))
+        .unwrap();
+    let owned = samples_data.to_owned();
+    for (channel, data) in owned.payload.iter().enumerate() {
+        let expected = (0..64)
+            .map(|i| (i + channel * 100) as f32)
+            .flat_map(f32::to_ne_bytes)
+            .collect::<Vec<_>>();
+        assert_eq!(data, &expected);
+    }
+
+    flow.destroy();
+}
+
+#[test]
+fn samples_stream_reader_reads_batches_in_order() {
+    let flow = audio_flow("samples_stream_reader");
+    let head_index = flow.current_index();
+    let TestFlow {
+        mxl_instance,
+        writer: samples_writer,
+        reader: samples_reader,
+        _domain_guard,
+        ..
+    } = flow;
+
+    samples_writer
+        .open_samples(head_index, 32)
+        .unwrap()
+        .commit()
+        .unwrap();
+    let mut stream_reader = mxl::SamplesStreamReader::new(samples_reader, 32).unwrap();
+    assert_eq!(stream_reader.next_index(), head_index + 32);
+
+    let writer_thread = std::thread::spawn(move || {
+        for batch in 1..=4u64 {
+            let mut samples_write_access = samples_writer
+                .open_samples(head_index + batch * 32, 32)
+                .unwrap();
+            for channel in 0..samples_write_access.channels() {
+                let (data_1, data_2) = samples_write_access.channel_data_f32_mut(channel).unwrap();
+                data_1.fill(batch as f32);
+                data_2.fill(batch as f32);
+            }
+            samples_write_access.commit().unwrap();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        samples_writer
+    });
+
+    for batch in 1..=4u32 {
+        let samples = stream_reader.next_batch(Duration::from_secs(5)).unwrap();
+        assert_eq!(samples.payload.len(), 2);
+        for channel in &samples.payload {
+            assert_eq!(channel.len(), 32 * 4);
+            assert!(
+                channel
+                    .chunks_exact(4)
+                    .all(|sample| f32::from_ne_bytes(sample.try_into().unwrap()) == batch as f32)
+            );
+        }
+    }
+    assert_eq!(stream_reader.skipped_samples(), 0);
+
+    let samples_writer = writer_thread.join().unwrap();
+    stream_reader.into_inner().destroy().unwrap();
+    samples_writer.destroy().unwrap();
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn head_index_advances_after_commit() {
+    let video = video_flow("head_index_grains");
+    let flow_reader = video
+        .mxl_instance
+        .create_flow_reader(video.config.common().id())
+        .unwrap();
+    let index = video.current_index();
+    for index in [index, index + 1] {
+        let grain_write_access = video.writer.open_grain(index).unwrap();
+        let total_slices = grain_write_access.total_slices();
+        grain_write_access.commit(total_slices).unwrap();
+        assert_eq!(video.reader.head_index().unwrap(), index);
+        assert_eq!(flow_reader.head_index().unwrap(), index);
+    }
+    drop(flow_reader);
+    video.destroy();
+
+    let audio = audio_flow("head_index_samples");
+    let index = audio.current_index();
+    for index in [index, index + 64] {
+        audio
+            .writer
+            .open_samples(index, 64)
+            .unwrap()
+            .commit()
+            .unwrap();
+        assert_eq!(audio.reader.head_index().unwrap(), index);
+    }
+    audio.destroy();
+}
+
+#[test]
+fn read_resync_jumps_to_head_when_behind() {
+    let flow = video_flow("read_resync");
+    let start_index = flow.current_index();
+    let grain_count = flow.config.discrete_info().unwrap().grain_count() as u64;
+    let last_index = start_index + grain_count + 4;
+    for index in start_index..=last_index {
+        let grain_write_access = flow.writer.open_grain(index).unwrap();
+        let total_slices = grain_write_access.total_slices();
+        grain_write_access.commit(total_slices).unwrap();
+    }
+
+    assert!(matches!(
+        flow.reader
+            .get_complete_grain(start_index, Duration::from_millis(100)),
+        Err(mxl::Error::OutOfRangeTooLate)
+    ));
+    let (index, _) = flow
+        .reader
+        .read_resync(start_index, Duration::from_secs(5))
+        .unwrap();
+    assert_eq!(index, last_index);
+    let (index, _) = flow
+        .reader
+        .read_resync(last_index, Duration::from_secs(5))
+        .unwrap();
+    assert_eq!(index, last_index);
+
+    flow.destroy();
+}
+
+#[test]
+fn invalid_utf8_flow_def_is_reported() {
+    let flow = video_flow("invalid_utf8_flow_def");
+    let flow_id = flow.config.common().id().to_string();
+    let flow_def_path = std::path::Path::new(flow.mxl_instance.domain())
+        .join(format!("{flow_id}.mxl-flow"))
+        .join("flow_def.json");
+    std::fs::write(&flow_def_path, b"{\"label\": \"\xff\xfe\"}").unwrap();
+    assert!(matches!(
+        flow.mxl_instance.get_flow_def(flow_id.as_str()),
+        Err(mxl::Error::Utf8(_))
+    ));
+    flow.destroy();
+}
+
+#[test]
+fn reload_api_requires_no_live_handles() {
+    let (mut mxl_instance, _domain_guard) = setup_test("reload_api");
+    let CreatedFlow {
+        writer: flow_writer,
+        config: flow_config_info,
+        ..
+    } = mxl_instance
+        .create_flow_writer(
+            read_flow_def("lib/tests/data/v210_flow.json").as_str(),
+            None,
+        )
+        .unwrap();
+    let flow_id = flow_config_info.common().id().to_string();
+    assert!(matches!(
+        mxl_instance.reload_api(get_mxl_so_path()),
+        Err(mxl::Error::Conflict)
+    ));
+
+    drop(flow_writer);
+    mxl_instance.reload_api(get_mxl_so_path()).unwrap();
+    // The domain persists across the reload.
+    assert!(mxl_instance.get_flow_def(flow_id.as_str()).is_ok());
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn open_grains_opens_all_or_fails() {
+    let flow = video_flow("open_grains");
+    let index = flow.current_index();
+
+    assert!(matches!(
+        flow.writer.open_grains(&[index, index + 1]),
+        Err(mxl::Error::Conflict)
+    ));
+    let grain_write_accesses = flow.writer.open_grains(&[index]).unwrap();
+    assert_eq!(grain_write_accesses.len(), 1);
+    for grain_write_access in grain_write_accesses {
+        let total_slices = grain_write_access.total_slices();
+        grain_write_access.commit(total_slices).unwrap();
+    }
+
+    flow.destroy();
+}
+
+#[test]
+fn recommended_write_batch_follows_flow_options() {
+    let (mxl_instance, _domain_guard) = setup_test("recommended_write_batch");
+    let CreatedFlow {
+        writer: flow_writer,
+        config: flow_config_info,
+        ..
+    } = mxl_instance
+        .create_flow_writer(
+            read_flow_def("lib/tests/data/audio_flow.json").as_str(),
+            Some(r#"{"maxCommitBatchSizeHint": 256, "maxSyncBatchSizeHint": 256}"#),
+        )
+        .unwrap();
+    assert_eq!(
+        flow_config_info.common().recommended_write_batch(),
+        Some(256)
+    );
+    drop(flow_writer);
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn duration_since_index_past_and_future() {
+    let (mxl_instance, _domain_guard) = setup_test("duration_since_index");
+    let rate = mxl::Rational {
+        numerator: 50,
+        denominator: 1,
+    };
+    let current_index = mxl_instance.get_current_index(&rate);
+    let elapsed = mxl_instance
+        .duration_since_index(current_index - 50, &rate)
+        .unwrap();
+    assert!(elapsed >= Duration::from_secs(1));
+    assert!(elapsed < Duration::from_secs(2));
+    assert_eq!(
+        mxl_instance
+            .duration_since_index(current_index + 50, &rate)
+            .unwrap(),
+        Duration::ZERO
+    );
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn index_drift_and_resync() {
+    let (mxl_instance, _domain_guard) = setup_test("index_drift");
+    let rate = mxl::Rational {
+        numerator: 30000,
+        denominator: 1001,
+    };
+    assert_eq!(
+        mxl_instance.index_drift(100, 110, &rate).unwrap(),
+        Duration::from_nanos(333_666_666)
+    );
+    assert_eq!(
+        mxl_instance.index_drift(110, 100, &rate).unwrap(),
+        Duration::from_nanos(333_666_666)
+    );
+
+    let current_index = mxl_instance.get_current_index(&rate);
+    let threshold = Duration::from_millis(100);
+    assert!(
+        mxl_instance
+            .should_resync(current_index - 30, &rate, threshold)
+            .unwrap()
+    );
+    assert!(
+        !mxl_instance
+            .should_resync(current_index + 1, &rate, threshold)
+            .unwrap()
+    );
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn uncompressed_video_grains_are_independent() {
+    let video = video_flow("grains_are_independent_video");
+    assert!(video.config.grains_are_independent());
+    video.destroy();
+
+    let audio = audio_flow("grains_are_independent_audio");
+    assert!(!audio.config.grains_are_independent());
+    audio.destroy();
+}
+
+#[test]
+fn failed_commit_cancels_grain() {
+    let flow = video_flow("failed_commit");
+    let index = flow.current_index();
+
+    // MXL only tracks the last opened grain, so committing the first one fails.
+    let first_grain = flow.writer.open_grain(index).unwrap();
+    let second_grain = flow.writer.open_grain(index + 1).unwrap();
+    let total_slices = first_grain.total_slices();
+    let result = first_grain.commit(total_slices);
+    assert!(matches!(
+        result,
+        Err(mxl::Error::CommitFailed { canceled: true, ref source })
+            if matches!(**source, mxl::Error::InvalidArg)
+    ));
+    drop(second_grain);
+
+    // The writer is usable again.
+    let grain_write_access = flow.writer.open_grain(index + 2).unwrap();
+    let total_slices = grain_write_access.total_slices();
+    grain_write_access.commit(total_slices).unwrap();
+
+    flow.destroy();
+}
+
+#[test]
+fn verified_grain_read_checks_crc() {
+    let flow = video_flow("verified_grain_read");
+    let index = flow.current_index();
+    let mut grain_write_access = flow.writer.open_grain(index).unwrap();
+    grain_write_access.fill_with(|i| (i * 7 % 256) as u8);
+    let expected = mxl::OwnedGrainData {
+        payload: grain_write_access.payload_mut().to_vec(),
+        flags: 0,
+    }
+    .crc32();
+    let total_slices = grain_write_access.total_slices();
+    grain_write_access.commit(total_slices).unwrap();
+
+    let grain = flow
+        .reader
+        .get_complete_grain_verified(index, Duration::from_secs(5), expected)
+        .unwrap();
+    assert_eq!(grain.crc32(), expected);
+    assert!(matches!(
+        flow.reader.get_complete_grain_verified(index, Duration::from_secs(5), !expected),
+        Err(mxl::Error::ChecksumMismatch { actual, .. }) if actual == expected
+    ));
+
+    flow.destroy();
+}
+
+#[test]
+fn instance_exposes_domain_and_options() {
+    let (mxl_instance, domain_guard) = setup_test("instance_domain");
+    assert_eq!(mxl_instance.domain(), domain_guard.domain());
+    assert_eq!(mxl_instance.options(), "");
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn opened_grain_payload_is_writable() {
+    let flow = video_flow("opened_grain_payload");
+    let mut grain_write_access = flow.writer.open_grain(flow.current_index()).unwrap();
+    let max_size = grain_write_access.max_size() as usize;
+    let payload = grain_write_access.payload_mut();
+    assert!(!payload.is_empty());
+    assert_eq!(payload.len(), max_size);
+    payload[0] = 0xAB;
+    payload[max_size - 1] = 0xCD;
+    assert_eq!((payload[0], payload[max_size - 1]), (0xAB, 0xCD));
+    grain_write_access.cancel().unwrap();
+
+    flow.destroy();
+}
+
+#[test]
+fn watch_flows_reports_added_and_removed_flows() {
+    let (mxl_instance, _domain_guard) = setup_test("watch_flows");
+    let (events, received) = std::sync::mpsc::channel();
+    let watcher = mxl_instance
+        .watch_flows(Duration::from_millis(10), move |event| {
+            let _ = events.send(event);
+        })
+        .unwrap();
+
+    let CreatedFlow {
+        writer: flow_writer,
+        config: flow_config_info,
+        ..
+    } = mxl_instance
+        .create_flow_writer(
+            read_flow_def("lib/tests/data/v210_flow.json").as_str(),
+            None,
+        )
+        .unwrap();
+    let flow_id = flow_config_info.common().id();
+    assert_eq!(
+        received.recv_timeout(Duration::from_secs(5)).unwrap(),
+        mxl::FlowEvent::Added(flow_id)
+    );
+
+    drop(flow_writer);
+    mxl_instance.delete_flow(flow_id).unwrap();
+    assert_eq!(
+        received.recv_timeout(Duration::from_secs(5)).unwrap(),
+        mxl::FlowEvent::Removed(flow_id)
+    );
+
+    drop(watcher);
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn samples_channel_peak_and_rms() {
+    let flow = audio_flow("samples_peak_rms");
+    let current_index = flow.current_index();
+
+    // One period of a triangle wave on the first channel, silence on the second one.
+    let wave = [0.0, 0.5, 1.0, 0.5, 0.0, -0.5, -1.0, -0.5];
+    let mut samples_write_access = flow.writer.open_samples(current_index, 8).unwrap();
+    for channel in 0..2 {
+        let (data_1, data_2) = samples_write_access.channel_data_f32_mut(channel).unwrap();
+        for (sample, value) in data_1.iter_mut().chain(data_2.iter_mut()).zip(wave) {
+            *sample = if channel == 0 { value } else { 0.0 };
+        }
+    }
+    samples_write_access.commit().unwrap();
+
+    let samples_data = flow
+        .reader
+        .get_samples(current_index, 8, Duration::from_secs(5))
+        .unwrap();
+    assert!((samples_data.channel_peak(0).unwrap() - 1.0).abs() < 1e-6);
+    assert!((samples_data.channel_rms(0).unwrap() - 0.375f32.sqrt()).abs() < 1e-6);
+    assert_eq!(samples_data.channel_peak(1).unwrap(), 0.0);
+    assert_eq!(samples_data.channel_rms(1).unwrap(), 0.0);
+    assert!(matches!(
+        samples_data.channel_peak(2),
+        Err(mxl::Error::InvalidArg)
+    ));
+
+    flow.destroy();
+}
+
+#[test]
+fn grain_read_policies() {
+    let flow = video_flow("grain_read_policies");
+    let index = flow.current_index();
+
+    let mut grain_write_access = flow.writer.open_grain(index).unwrap();
+    let total_slices = grain_write_access.total_slices();
+    grain_write_access.commit_slices(total_slices / 2).unwrap();
+
+    assert!(matches!(
+        flow.reader.get_grain_with_policy(
+            index,
+            mxl::GrainReadPolicy::RequireComplete,
+            Duration::from_millis(50)
+        ),
+        Err(mxl::Error::Timeout)
+    ));
+    let (_, slices) = flow
+        .reader
+        .get_grain_with_policy(
+            index,
+            mxl::GrainReadPolicy::AcceptPartial,
+            Duration::from_secs(5),
+        )
+        .unwrap();
+    assert!(!slices.is_complete());
+    assert_eq!(
+        slices,
+        mxl::GrainSlices {
+            committed: total_slices / 2,
+            total: total_slices
+        }
+    );
+    let start = std::time::Instant::now();
+    let (_, slices) = flow
+        .reader
+        .get_grain_with_policy(
+            index,
+            mxl::GrainReadPolicy::AcceptAfter(Duration::from_millis(50)),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+    assert_eq!(slices.committed, total_slices / 2);
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    grain_write_access.commit(total_slices).unwrap();
+    for policy in [
+        mxl::GrainReadPolicy::RequireComplete,
+        mxl::GrainReadPolicy::AcceptPartial,
+        mxl::GrainReadPolicy::AcceptAfter(Duration::from_millis(50)),
+    ] {
+        let (grain, slices) = flow
+            .reader
+            .get_grain_with_policy(index, policy, Duration::from_secs(5))
+            .unwrap();
+        assert!(slices.is_complete());
+        assert_eq!(slices.committed, total_slices);
+        assert_eq!(grain.payload.len(), grain.total_size);
+    }
+
+    flow.destroy();
+}
+
+#[test]
+fn grain_writer_observes_reads() {
+    let flow = video_flow("grain_writer_runtime_info");
+    let flow_id = flow.config.common().id();
+    let index = flow.current_index();
+    assert_eq!(flow.writer.id(), flow_id);
+
+    let grain_write_access = flow.writer.open_grain(index).unwrap();
+    let total_slices = grain_write_access.total_slices();
+    grain_write_access.commit(total_slices).unwrap();
+    let runtime_info = flow.writer.get_runtime_info().unwrap();
+    assert_eq!(runtime_info.headIndex, index);
+    let last_read_time = runtime_info.lastReadTime;
+
+    std::thread::sleep(Duration::from_millis(10));
+    flow.reader
+        .get_complete_grain(index, Duration::from_secs(5))
+        .unwrap();
+    // MXL updates the last read time asynchronously, from the domain watcher thread.
+    let deadline = std::time::Instant::now() + Duration::from_secs(1);
+    while flow.writer.get_runtime_info().unwrap().lastReadTime <= last_read_time {
+        assert!(
+            std::time::Instant::now() < deadline,
+            "Last read time not updated."
+        );
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    flow.destroy();
+}
+
+#[test]
+fn create_flow_reader_with_options() {
+    let (mxl_instance, _domain_guard) = setup_test("flow_reader_options");
+    let CreatedFlow {
+        writer: flow_writer,
+        config: flow_config_info,
+        ..
+    } = mxl_instance
+        .create_flow_writer(
+            read_flow_def("lib/tests/data/v210_flow.json").as_str(),
+            None,
+        )
+        .unwrap();
+    let flow_id = flow_config_info.common().id().to_string();
+
+    for options in ["", r#"{"urn:x-mxl:option:test/v1.0": "value"}"#] {
+        let flow_reader = mxl_instance
+            .create_flow_reader_with_options(&flow_id, options)
+            .unwrap();
+        assert_eq!(flow_reader.flow_id().to_string(), flow_id);
+    }
+    assert!(matches!(
+        mxl_instance.create_flow_reader_with_options(&flow_id, "\0"),
+        Err(mxl::Error::NulString(_))
+    ));
+
+    drop(flow_writer);
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn try_destroy_returns_instance_in_use() {
+    let (mxl_instance, _domain_guard) = setup_test("try_destroy");
+    let CreatedFlow {
+        writer: flow_writer,
+        config: flow_config_info,
+        ..
+    } = mxl_instance
+        .create_flow_writer(
+            read_flow_def("lib/tests/data/v210_flow.json").as_str(),
+            None,
+        )
+        .unwrap();
+    let flow_reader = mxl_instance
+        .create_flow_reader(flow_config_info.common().id())
+        .unwrap();
+
+    let (mxl_instance, error) = mxl_instance.try_destroy().unwrap_err();
+    assert!(matches!(error, mxl::Error::Other(_)));
+
+    drop(flow_reader);
+    drop(flow_writer);
+    assert!(mxl_instance.try_destroy().is_ok());
+}
+
+#[test]
+fn oldest_available_index_is_within_buffer_depth() {
+    let (mxl_instance, _domain_guard) = setup_test("oldest_available_index");
+    for flow_def in [
+        "lib/tests/data/v210_flow.json",
+        "lib/tests/data/audio_flow.json",
+    ] {
+        let CreatedFlow {
+            writer: flow_writer,
+            config: flow_config_info,
+            ..
+        } = mxl_instance
+            .create_flow_writer(read_flow_def(flow_def).as_str(), None)
+            .unwrap();
+        let depth = flow_config_info.buffer_depth().unwrap();
+        if flow_config_info.is_discrete_flow() {
+            assert_eq!(
+                depth,
+                u64::from(flow_config_info.discrete_info().unwrap().grain_count())
+            );
+        } else {
+            assert_eq!(
+                depth,
+                u64::from(flow_config_info.continuous().unwrap().bufferLength) / 2
+            );
+        }
+
+        let flow_reader = mxl_instance
+            .create_flow_reader(flow_config_info.common().id())
+            .unwrap();
+        let oldest_index = flow_reader.oldest_available_index().unwrap();
+        let head_index = flow_reader.head_index().unwrap();
+        assert!(oldest_index <= head_index);
+        assert!(head_index - oldest_index < depth);
+
+        drop(flow_reader);
+        drop(flow_writer);
+    }
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn samples_read_into_reuses_buffers() {
+    let flow = audio_flow("samples_read_into");
+    let current_index = flow.current_index();
+    let batch_size = 64;
+
+    let mut out = Vec::new();
+    let mut capacities = Vec::new();
+    for batch in 0..3 {
+        let index = current_index + batch * batch_size as u64;
+        let mut samples_write_access = flow.writer.open_samples(index, batch_size).unwrap();
+        for channel in 0..samples_write_access.channels() {
+            let (data_1, data_2) = samples_write_access.channel_data_mut(channel).unwrap();
+            data_1.fill(batch as u8 + 1);
+            data_2.fill(batch as u8 + 1);
+        }
+        samples_write_access.commit().unwrap();
+
+        flow.reader
+            .read_into(index, batch_size, Duration::from_secs(5), &mut out)
+            .unwrap();
+        assert_eq!(out.len(), 2);
+        let expected = flow
+            .reader
+            .get_samples(index, batch_size, Duration::from_secs(5))
+            .unwrap()
+            .to_owned();
+        assert_eq!(out, expected.payload);
+        assert!(out[0].iter().all(|byte| *byte == batch as u8 + 1));
+        if batch == 0 {
+            capacities = out.iter().map(Vec::capacity).collect();
+        } else {
+            assert_eq!(
+                out.iter().map(Vec::capacity).collect::<Vec<_>>(),
+                capacities
+            );
+        }
+    }
+
+    flow.destroy();
+}
+
+#[test]
+fn grain_read_relative_to_head() {
+    let flow = video_flow("grain_get_relative");
+    let current_index = flow.current_index();
+
+    for index in current_index..current_index + 3 {
+        let mut grain_write_access = flow.writer.open_grain(index).unwrap();
+        let total_slices = grain_write_access.total_slices();
+        grain_write_access.payload_mut().fill(index as u8);
+        grain_write_access.commit(total_slices).unwrap();
+    }
+    let head_index = current_index + 2;
+
+    let (index, grain) = flow.reader.get_relative(0, Duration::from_secs(5)).unwrap();
+    assert_eq!(index, head_index);
+    assert!(grain.payload.iter().all(|byte| *byte == head_index as u8));
+    let (index, grain) = flow.reader.get_relative(1, Duration::from_secs(5)).unwrap();
+    assert_eq!(index, head_index - 1);
+    assert!(
+        grain
+            .payload
+            .iter()
+            .all(|byte| *byte == (head_index - 1) as u8)
+    );
+
+    flow.destroy();
+}
+
+#[test]
+fn create_flow_writer_with_typed_options() {
+    let (mxl_instance, _domain_guard) = setup_test("create_flow_writer_opts");
+    let options = mxl::WriterOptions::new()
+        .max_commit_batch_size_hint(128)
+        .max_sync_batch_size_hint(256);
+    let CreatedFlow {
+        writer: flow_writer,
+        config: flow_config_info,
+        ..
+    } = mxl_instance
+        .create_flow_writer_opts(
+            read_flow_def("lib/tests/data/audio_flow.json").as_str(),
+            &options,
+        )
+        .unwrap();
+    assert_eq!(flow_config_info.common().max_commit_batch_size_hint(), 128);
+    assert_eq!(flow_config_info.common().max_sync_batch_size_hint(), 256);
+    drop(flow_writer);
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn drop_error_handler_is_not_called_on_clean_drops() {
+    static DROP_ERRORS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+    mxl::set_drop_error_handler(|_| {
+        DROP_ERRORS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    });
+
+    let flow = video_flow("drop_error_handler");
+    // Canceled on drop.
+    drop(flow.writer.open_grain(flow.current_index()).unwrap());
+    drop(flow.reader);
+    drop(flow.writer);
+    flow.mxl_instance.destroy().unwrap();
+
+    assert_eq!(DROP_ERRORS.load(std::sync::atomic::Ordering::SeqCst), 0);
+}
+
+#[test]
+fn prefetching_grain_reader_reads_in_order() {
+    let flow = video_flow("prefetching_grain_reader");
+    let current_index = flow.current_index();
+    let write_grain = |index: u64| {
+        let mut grain_write_access = flow.writer.open_grain(index).unwrap();
+        let total_slices = grain_write_access.total_slices();
+        grain_write_access.payload_mut().fill(index as u8);
+        grain_write_access.commit(total_slices).unwrap();
+    };
+    write_grain(current_index);
+    let mut prefetcher = mxl::PrefetchingGrainReader::new(flow.reader, 3).unwrap();
+    let first_index = prefetcher.next_index();
+    assert_eq!(first_index, current_index + 1);
+
+    // Keep the writer a few grains ahead, so that the prefetcher has something to fetch.
+    for index in first_index..first_index + 3 {
+        write_grain(index);
+    }
+    for index in first_index..first_index + 10 {
+        write_grain(index + 3);
+        assert_eq!(prefetcher.next_index(), index);
+        let grain = prefetcher.next().unwrap();
+        assert!(grain.payload.iter().all(|byte| *byte == index as u8));
+    }
+
+    drop(prefetcher);
+    flow.writer.destroy().unwrap();
+    flow.mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn flow_reader_reports_discrete_and_continuous_flows() {
+    let (mxl_instance, _domain_guard) = setup_test("flow_reader_is_discrete");
+    for flow_def in [
+        "lib/tests/data/v210_flow.json",
+        "lib/tests/data/audio_flow.json",
+    ] {
+        let CreatedFlow {
+            writer: flow_writer,
+            config: flow_config_info,
+            ..
+        } = mxl_instance
+            .create_flow_writer(read_flow_def(flow_def).as_str(), None)
+            .unwrap();
+        let flow_reader = mxl_instance
+            .create_flow_reader(flow_config_info.common().id())
+            .unwrap();
+        assert_eq!(
+            flow_reader.is_discrete().unwrap(),
+            flow_config_info.is_discrete_flow()
+        );
+        assert_eq!(
+            flow_reader.is_continuous().unwrap(),
+            !flow_config_info.is_discrete_flow()
+        );
+        // The reader can still be converted afterwards.
+        if flow_reader.is_discrete().unwrap() {
+            flow_reader.to_grain_reader().unwrap();
+        } else {
+            flow_reader.to_samples_reader().unwrap();
+        }
+        drop(flow_writer);
+    }
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn grain_read_at_timestamp() {
+    let flow = video_flow("grain_at_timestamp");
+    let rate = flow.config.common().grain_rate().unwrap();
+    let current_index = flow.current_index();
+    let mut grain_write_access = flow.writer.open_grain(current_index).unwrap();
+    let total_slices = grain_write_access.total_slices();
+    grain_write_access.payload_mut().fill(0x5a);
+    grain_write_access.commit(total_slices).unwrap();
+
+    // A quarter of the grain period after the grain is due, still within the grain.
+    let timestamp = mxl::Timestamp::from_nanos(
+        flow.mxl_instance
+            .index_to_timestamp(current_index, &rate)
+            .unwrap()
+            .as_nanos()
+            + mxl::RationalExt::period(&rate).unwrap().as_nanos() as u64 / 4,
+    );
+    let (index, grain) = flow
+        .reader
+        .get_at_timestamp(timestamp, Duration::from_secs(5))
+        .unwrap();
+    assert_eq!(
+        index,
+        flow.mxl_instance
+            .timestamp_to_index(timestamp, &rate)
+            .unwrap()
+    );
+    assert_eq!(index, current_index);
+    assert!(grain.payload.iter().all(|byte| *byte == 0x5a));
+
+    flow.destroy();
+}
+
+#[test]
+fn flush_before_commit() {
+    let video = video_flow("flush_before_commit_grains");
+    let index = video.current_index();
+    let mut grain_write_access = video.writer.open_grain(index).unwrap();
+    let total_slices = grain_write_access.total_slices();
+    grain_write_access.payload_mut().fill(0xa5);
+    grain_write_access.flush().unwrap();
+    grain_write_access.commit(total_slices).unwrap();
+    let grain = video
+        .reader
+        .get_complete_grain(index, Duration::from_secs(5))
+        .unwrap();
+    assert!(grain.payload.iter().all(|byte| *byte == 0xa5));
+    video.destroy();
+
+    let audio = audio_flow("flush_before_commit_samples");
+    let index = audio.current_index();
+    let mut samples_write_access = audio.writer.open_samples(index, 16).unwrap();
+    for channel in 0..samples_write_access.channels() {
+        let (data_1, data_2) = samples_write_access.channel_data_mut(channel).unwrap();
+        data_1.fill(0x5a);
+        data_2.fill(0x5a);
+    }
+    samples_write_access.flush().unwrap();
+    samples_write_access.commit().unwrap();
+    let samples = audio
+        .reader
+        .get_samples(index, 16, Duration::from_secs(5))
+        .unwrap()
+        .to_owned();
+    assert!(samples.payload.iter().flatten().all(|byte| *byte == 0x5a));
+    audio.destroy();
+}
+
+#[test]
+fn clock_offset_is_stable() {
+    let (mxl_instance, _domain_guard) = setup_test("clock_offset");
+    let first = mxl_instance.clock_offset();
+    std::thread::sleep(Duration::from_millis(10));
+    let second = mxl_instance.clock_offset();
+    assert!(first.abs_diff(second) < Duration::from_millis(5));
+
+    let now = mxl_instance.timestamp_to_system_time(mxl_instance.get_time());
+    let system_now = std::time::SystemTime::now();
+    let difference = system_now
+        .duration_since(now)
+        .unwrap_or_else(|error| error.duration());
+    assert!(difference < Duration::from_millis(5));
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn grain_read_timeout_sentinels() {
+    let flow = video_flow("timeout_sentinels");
+    let index = flow.current_index();
+    let TestFlow {
+        mxl_instance,
+        writer: grain_writer,
+        reader: grain_reader,
+        _domain_guard,
+        ..
+    } = flow;
+
+    let grain_write_access = grain_writer.open_grain(index).unwrap();
+    let total_slices = grain_write_access.total_slices();
+    grain_write_access.commit(total_slices).unwrap();
+
+    // Ready grain.
+    for timeout in [
+        Timeout::Immediate,
+        Timeout::For(Duration::from_millis(50)),
+        Timeout::Forever,
+    ] {
+        grain_reader.get_complete_grain(index, timeout).unwrap();
+    }
+
+    // Unready grain.
+    assert!(matches!(
+        grain_reader.get_complete_grain(index + 1, Timeout::Immediate),
+        Err(mxl::Error::OutOfRangeTooEarly)
+    ));
+    assert!(matches!(
+        grain_reader.get_complete_grain(index + 1, Timeout::For(Duration::from_millis(50))),
+        Err(mxl::Error::OutOfRangeTooEarly | mxl::Error::Timeout)
+    ));
+    let writer_thread = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        let grain_write_access = grain_writer.open_grain(index + 1).unwrap();
+        let total_slices = grain_write_access.total_slices();
+        grain_write_access.commit(total_slices).unwrap();
+        grain_writer
+    });
+    grain_reader
+        .get_complete_grain(index + 1, Timeout::Forever)
+        .unwrap();
+    let grain_writer = writer_thread.join().unwrap();
+
+    grain_reader.destroy().unwrap();
+    grain_writer.destroy().unwrap();
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn caching_samples_reader_fetches_config_once() {
+    let mut flow = audio_flow("caching_samples_reader");
+    let mut index = flow.current_index();
+    let expected_rate = flow.config.common().sample_rate().unwrap();
+    let mut samples_reader = CachingSamplesReader::new(flow.reader).unwrap();
+    let config = format!("{:?}", samples_reader.config());
+    let rate = samples_reader.sample_rate().unwrap();
+    assert_eq!(
+        (rate.numerator, rate.denominator),
+        (expected_rate.numerator, expected_rate.denominator)
+    );
+
+    for _ in 0..10 {
+        flow.writer
+            .open_samples(index, 16)
+            .unwrap()
+            .commit()
+            .unwrap();
+        assert_eq!(samples_reader.head_index().unwrap(), index);
+        samples_reader
+            .get_samples(index, 16, Duration::from_secs(5))
+            .unwrap();
+        index += 16;
+    }
+    assert_eq!(samples_reader.config_fetches(), 1);
+    assert_eq!(format!("{:?}", samples_reader.config()), config);
+    samples_reader.refresh_config().unwrap();
+    assert_eq!(samples_reader.config_fetches(), 2);
+    assert_eq!(format!("{:?}", samples_reader.config()), config);
+
+    flow.reader = samples_reader.into_inner();
+    flow.destroy();
+}
+
+#[test]
+fn get_flow_def_larger_than_initial_buffer() {
+    let mut flow_def: serde_json::Value =
+        serde_json::from_str(&read_flow_def("lib/tests/data/v210_flow.json")).unwrap();
+    // Well past the 4096 bytes tried first, and past twice as much.
+    let tags = flow_def["tags"].as_object_mut().unwrap();
+    for i in 0..500 {
+        tags.insert(
+            format!("urn:x-mxl:tag:test-{i}/v1.0"),
+            serde_json::json!([format!("value-{i}")]),
+        );
+    }
+    let flow_def = serde_json::to_string_pretty(&flow_def).unwrap();
+    assert!(flow_def.len() > 4 * 4096);
+    let flow = video_flow_with_def("large_flow_def", &flow_def);
+    let flow_id = flow.config.common().id().to_string();
+    let retrieved_flow_def = flow.mxl_instance.get_flow_def(flow_id.as_str()).unwrap();
+    assert_eq!(flow_def, retrieved_flow_def);
+    flow.destroy();
+}
+
+#[test]
+fn invalid_flow_id_is_rejected_before_mxl() {
+    let (mxl_instance, _domain_guard) = setup_test("invalid_flow_id");
+    // MXL would report a missing flow, the id is rejected before it gets the chance to.
+    assert!(matches!(
+        mxl_instance.create_flow_reader("not-a-flow-id"),
+        Err(mxl::Error::InvalidFlowId(_))
+    ));
+    assert!(matches!(
+        mxl_instance.flow_exists("not-a-flow-id"),
+        Err(mxl::Error::InvalidFlowId(_))
+    ));
+    assert!(matches!(
+        mxl_instance.get_flow_def("not-a-flow-id"),
+        Err(mxl::Error::InvalidFlowId(_))
+    ));
+    mxl_instance.destroy().unwrap();
+}
+
+/// A pipeline stage generic over the backends: copies the grain at the head of `source` to `sink`.
+fn forward_head_grain(
+    source: impl mxl::GrainSource,
+    sink: impl mxl::GrainSink,
+) -> mxl::Result<u64> {
+    let index = source.head_index()?;
+    let grain = source.read_grain(index, Timeout::For(Duration::from_secs(1)))?;
+    sink.write_grain(index, &grain.payload)?;
+    Ok(index)
+}
+
+#[test]
+fn generic_pipeline_over_grain_reader_and_writer() {
+    let source = video_flow("generic_pipeline_source");
+    // The same flow in another domain, so that the grain sizes match.
+    let sink = video_flow("generic_pipeline_sink");
+
+    let index = source.current_index();
+    let grain_size = source.writer.open_grain(index).unwrap().max_size() as usize;
+    source
+        .writer
+        .write_grain(index, &vec![0x3c; grain_size])
+        .unwrap();
+    assert_eq!(
+        forward_head_grain(&source.reader, &sink.writer).unwrap(),
+        index
+    );
+    let grain = sink
+        .reader
+        .get_complete_grain(index, Duration::from_secs(5))
+        .unwrap();
+    assert!(grain.payload.iter().all(|byte| *byte == 0x3c));
+
+    sink.destroy();
+    source.destroy();
+}
+
+#[test]
+fn samples_available_range_follows_writer() {
+    let flow = audio_flow("samples_available_range");
+    let depth = flow.config.buffer_depth().unwrap();
+    let batch_size = depth / 4;
+    let mut index = flow.current_index();
+
+    // Fill the whole readable half of the ring buffer, and some more.
+    for _ in 0..8 {
+        flow.writer
+            .open_samples(index, batch_size as usize)
+            .unwrap()
+            .commit()
+            .unwrap();
+        index += batch_size;
+    }
+    let head_index = flow.reader.head_index().unwrap();
+    let range = flow.reader.available_range().unwrap();
+    assert!(range.contains(&head_index));
+    assert_eq!(range.end, head_index + 1);
+    assert_eq!(range.end - range.start, depth);
+    flow.reader
+        .get_samples_non_blocking(range.end - 1, batch_size as usize)
+        .unwrap();
+    // The oldest batch which can be read ends `batch_size - 1` samples after the range start.
+    flow.reader
+        .get_samples_non_blocking(range.start + batch_size - 1, batch_size as usize)
+        .unwrap();
+
+    flow.destroy();
+}
+
+#[test]
+fn samples_default_read_timeout_is_applied() {
+    let mut flow = audio_flow("samples_default_read_timeout");
+    let index = flow.current_index();
+    flow.writer
+        .open_samples(index, 16)
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    let timeout = Duration::from_millis(200);
+    flow.reader.set_default_timeout(timeout);
+    let start = std::time::Instant::now();
+    let result = flow.reader.get_samples_default(index + 16, 16);
+    let elapsed = start.elapsed();
+    assert!(matches!(result, Err(mxl::Error::Timeout)));
+    assert!(elapsed >= timeout);
+    assert!(elapsed < timeout * 5);
+    assert!(matches!(
+        flow.reader.get_samples(index + 16, 16, Timeout::Immediate),
+        Err(mxl::Error::OutOfRangeTooEarly)
+    ));
+
+    flow.destroy();
+}
+
+#[test]
+fn gc_stale_flows_skips_broken_flows() {
+    let (mxl_instance, _domain_guard) = setup_test("gc_broken_flows");
+    // What a writer crashing while creating its flow could leave behind.
+    let broken_flow_id = uuid::Uuid::new_v4();
+    std::fs::create_dir(
+        std::path::Path::new(mxl_instance.domain()).join(format!("{broken_flow_id}.mxl-flow")),
+    )
+    .unwrap();
+    let flow = mxl_instance
+        .create_flow_writer(
+            read_flow_def("lib/tests/data/audio_flow.json").as_str(),
+            None,
+        )
+        .unwrap();
+    let flow_id = flow.config.common().id();
+    drop(flow);
+
+    let removed = mxl_instance.gc_stale_flows(Duration::ZERO).unwrap();
+    assert!(removed.contains(&flow_id));
+    assert!(!removed.contains(&broken_flow_id));
+    mxl_instance.destroy().unwrap();
+}
+
+#[test]
+fn flow_reader_clone_keeps_options() {
+    let (mxl_instance, _domain_guard) = setup_test("flow_reader_clone_options");
+    let flow = mxl_instance
+        .create_flow_writer(
+            read_flow_def("lib/tests/data/v210_flow.json").as_str(),
+            None,
+        )
+        .unwrap();
+    let options = r#"{"urn:x-mxl:option:unknown/v1.0": 1}"#;
+    let flow_reader = mxl_instance
+        .create_flow_reader_with_options(flow.config.common().id(), options)
+        .unwrap();
+    assert_eq!(flow_reader.options(), options);
+    assert_eq!(flow_reader.try_clone().unwrap().options(), options);
+    assert_eq!(
+        mxl_instance
+            .create_flow_reader(flow.config.common().id())
+            .unwrap()
+            .options(),
+        ""
+    );
+    drop(flow_reader);
+    drop(flow);
+    mxl_instance.destroy().unwrap();
+}
@@ -0,0 +1,26 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use mxl::DataFormat;
+
+const ALL_FORMATS: [DataFormat; 4] = [
+    DataFormat::Unspecified,
+    DataFormat::Video,
+    DataFormat::Audio,
+    DataFormat::Data,
+];
+
+#[test]
+fn u32_round_trip() {
+    for format in ALL_FORMATS {
+        assert_eq!(DataFormat::from(format.as_u32()), format);
+    }
+}
+
+#[test]
+fn string_round_trip() {
+    for format in ALL_FORMATS {
+        assert_eq!(format.to_string().parse::<DataFormat>().unwrap(), format);
+    }
+    assert!("mux".parse::<DataFormat>().is_err());
+}
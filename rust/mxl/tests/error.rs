@@ -0,0 +1,40 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use mxl::Error;
+
+#[test]
+fn unknown_status_code_is_preserved() {
+    let status = 4242;
+    let error = Error::from_status(status).unwrap_err();
+    assert!(matches!(error, Error::Unknown(code) if code == status));
+    assert!(error.to_string().contains("4242"));
+}
+
+#[test]
+fn ok_status_is_not_an_error() {
+    assert!(Error::from_status(mxl_sys::MXL_STATUS_OK).is_ok());
+}
+
+#[test]
+fn errors_convert_to_io_errors() {
+    let timeout = std::io::Error::from(Error::Timeout);
+    assert_eq!(timeout.kind(), std::io::ErrorKind::TimedOut);
+    assert_eq!(timeout.to_string(), Error::Timeout.to_string());
+
+    let not_found = std::io::Error::from(Error::FlowNotFound);
+    assert_eq!(not_found.kind(), std::io::ErrorKind::NotFound);
+    assert!(matches!(
+        not_found
+            .into_inner()
+            .unwrap()
+            .downcast::<Error>()
+            .as_deref(),
+        Ok(Error::FlowNotFound)
+    ));
+
+    assert_eq!(
+        std::io::Error::from(Error::Conflict).kind(),
+        std::io::ErrorKind::Other
+    );
+}
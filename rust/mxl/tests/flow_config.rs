@@ -0,0 +1,78 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use mxl::{CommonFlowConfigInfo, Rational};
+
+fn audio_config(commit_hint: u32, sync_hint: u32) -> mxl_sys::CommonFlowConfigInfo {
+    mxl_sys::CommonFlowConfigInfo {
+        format: mxl_sys::MXL_DATA_FORMAT_AUDIO,
+        grainRate: Rational {
+            numerator: 48000,
+            denominator: 1,
+        },
+        maxCommitBatchSizeHint: commit_hint,
+        maxSyncBatchSizeHint: sync_hint,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn recommended_read_batch_precedence() {
+    let config = audio_config(64, 256);
+    assert_eq!(
+        CommonFlowConfigInfo::from(&config).recommended_read_batch(),
+        256
+    );
+
+    let config = audio_config(64, 0);
+    assert_eq!(
+        CommonFlowConfigInfo::from(&config).recommended_read_batch(),
+        64
+    );
+
+    let config = audio_config(0, 128);
+    assert_eq!(
+        CommonFlowConfigInfo::from(&config).recommended_read_batch(),
+        128
+    );
+
+    // 10 ms at 48 kHz.
+    let config = audio_config(0, 0);
+    assert_eq!(
+        CommonFlowConfigInfo::from(&config).recommended_read_batch(),
+        480
+    );
+}
+
+#[test]
+fn recommended_read_batch_is_never_zero() {
+    let mut config = audio_config(0, 0);
+    config.grainRate = Rational {
+        numerator: 25,
+        denominator: 1,
+    };
+    assert_eq!(
+        CommonFlowConfigInfo::from(&config).recommended_read_batch(),
+        1
+    );
+    config.grainRate.denominator = 0;
+    assert_eq!(
+        CommonFlowConfigInfo::from(&config).recommended_read_batch(),
+        1
+    );
+}
+
+#[test]
+fn recommended_write_batch_is_the_commit_hint() {
+    let config = audio_config(64, 256);
+    assert_eq!(
+        CommonFlowConfigInfo::from(&config).recommended_write_batch(),
+        Some(64)
+    );
+
+    let config = audio_config(0, 256);
+    assert_eq!(
+        CommonFlowConfigInfo::from(&config).recommended_write_batch(),
+        None
+    );
+}
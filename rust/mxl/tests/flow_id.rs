@@ -0,0 +1,24 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use mxl::{Error, FlowId};
+use uuid::Uuid;
+
+#[test]
+fn flow_id_round_trips_through_strings() {
+    let text = "5fbec3b1-1b0f-417d-9059-8b94a47197ed";
+    let flow_id: FlowId = text.parse().unwrap();
+    assert_eq!(flow_id.to_string(), text);
+    assert_eq!(flow_id.as_hyphenated_string(), text);
+    assert_eq!(FlowId::try_from(text.to_uppercase()).unwrap(), flow_id);
+    assert_eq!(Uuid::from(flow_id), Uuid::parse_str(text).unwrap());
+}
+
+#[test]
+fn invalid_flow_id_is_rejected() {
+    assert!(matches!(
+        "not-a-flow-id".parse::<FlowId>(),
+        Err(Error::InvalidFlowId(_))
+    ));
+    assert!(matches!(FlowId::try_from(""), Err(Error::InvalidFlowId(_))));
+}
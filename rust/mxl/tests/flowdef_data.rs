@@ -1,7 +1,7 @@
 // SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
 // SPDX-License-Identifier: Apache-2.0
 
-use mxl::flowdef::{FlowDef, FlowDefData, FlowDefDetails, Rate};
+use mxl::flowdef::{FlowDef, FlowDefData, FlowDefDetails, InterlaceMode, Rate};
 use std::collections::HashMap;
 use uuid::Uuid;
 
@@ -41,10 +41,8 @@ fn flow_def_data_details_roundtrip_serde() {
     assert_eq!(details, parsed);
 }
 
-/// Full [`FlowDef`] is not serde round-tripped here: `format` on the struct and
-/// `#[serde(tag = "format")]` on flattened [`FlowDefDetails`] duplicate the JSON
-/// key on serialize (same for video and audio). MXL code serializes to JSON for
-/// the C API; use this test only to lock data-flow JSON shape.
+/// Locks the data-flow JSON shape. The `format` key is produced by the
+/// `#[serde(tag = "format")]` on flattened [`FlowDefDetails`].
 #[test]
 fn flow_def_data_serializes_like_mxlsink() {
     let flow = FlowDef {
@@ -54,7 +52,6 @@ fn flow_def_data_serializes_like_mxlsink() {
             "urn:x-nmos:tag:grouphint/v1.0".to_string(),
             vec!["test:Ancillary Data".to_string()],
         )]),
-        format: "urn:x-nmos:format:data".into(),
         label: "Short description of the data flow".into(),
         parents: vec![],
         media_type: "video/smpte291".into(),
@@ -74,4 +71,127 @@ fn flow_def_data_serializes_like_mxlsink() {
     );
     assert_eq!(v["media_type"].as_str(), Some("video/smpte291"));
     assert_eq!(v["grain_rate"]["numerator"], 50);
+    assert_eq!(json.matches("\"format\"").count(), 1);
+}
+
+#[test]
+fn flow_def_json_roundtrip() {
+    for fixture in [
+        include_str!("../../../lib/tests/data/v210_flow.json"),
+        include_str!("../../../lib/tests/data/audio_flow.json"),
+        include_str!("../../../lib/tests/data/data_flow.json"),
+    ] {
+        let flow_def = FlowDef::from_json(fixture).expect("fixture parses as FlowDef");
+        assert_eq!(flow_def.format(), flow_def.details.format());
+        let json = flow_def.to_json().expect("serialize FlowDef");
+        assert_eq!(
+            FlowDef::from_json(&json).expect("reparse FlowDef"),
+            flow_def
+        );
+    }
+}
+
+#[test]
+fn nmos_raw_video_flow_maps_to_v210() {
+    let raw = include_str!("data/nmos_raw_video_flow.json");
+    let flow_def = FlowDef::from_nmos(raw).expect("raw video NMOS flow is supported");
+    assert_eq!(flow_def.media_type, "video/v210");
+    assert_eq!(flow_def.format(), "urn:x-nmos:format:video");
+    let FlowDefDetails::Video(video) = &flow_def.details else {
+        panic!("video details expected");
+    };
+    assert_eq!(
+        video.grain_rate,
+        Rate {
+            numerator: 50,
+            denominator: 1,
+        }
+    );
+    assert_eq!((video.frame_width, video.frame_height), (1920, 1080));
+    assert_eq!(video.interlace_mode, InterlaceMode::InterlacedTff);
+}
+
+#[test]
+fn nmos_l24_audio_flow_maps_to_float32() {
+    let raw = include_str!("data/nmos_l24_audio_flow.json");
+    let flow_def = FlowDef::from_nmos(raw).expect("L24 audio NMOS flow is supported");
+    assert_eq!(flow_def.media_type, "audio/float32");
+    let FlowDefDetails::Audio(audio) = &flow_def.details else {
+        panic!("audio details expected");
+    };
+    assert_eq!(
+        audio.sample_rate,
+        Rate {
+            numerator: 48000,
+            denominator: 1,
+        }
+    );
+    assert_eq!(audio.channel_count, 2);
+    assert_eq!(audio.bit_depth, 32);
+}
+
+#[test]
+fn nmos_unsupported_flows_are_rejected() {
+    let raw = include_str!("data/nmos_raw_video_flow.json");
+    let mut v: serde_json::Value = serde_json::from_str(raw).unwrap();
+    v["media_type"] = "video/H264".into();
+    assert!(matches!(
+        FlowDef::from_nmos(&v.to_string()),
+        Err(mxl::Error::Other(_))
+    ));
+
+    let mut v: serde_json::Value = serde_json::from_str(raw).unwrap();
+    v["components"][0]["bit_depth"] = 8.into();
+    assert!(FlowDef::from_nmos(&v.to_string()).is_err());
+
+    let raw = include_str!("data/nmos_l24_audio_flow.json");
+    let mut v: serde_json::Value = serde_json::from_str(raw).unwrap();
+    v.as_object_mut().unwrap().remove("channel_count");
+    assert!(FlowDef::from_nmos(&v.to_string()).is_err());
+}
+
+fn validation_error(flow_def: &FlowDef) -> String {
+    match flow_def.validate() {
+        Err(mxl::Error::Other(message)) => message,
+        other => panic!("validation error expected, got {other:?}"),
+    }
+}
+
+#[test]
+fn flow_def_validation_reports_invalid_values() {
+    let raw = include_str!("data/nmos_l24_audio_flow.json");
+    let flow_def = FlowDef::from_nmos(raw).unwrap();
+    assert!(flow_def.validate().is_ok());
+
+    let mut zero_channels = flow_def.clone();
+    let FlowDefDetails::Audio(audio) = &mut zero_channels.details else {
+        panic!("audio details expected");
+    };
+    audio.channel_count = 0;
+    assert_eq!(
+        validation_error(&zero_channels),
+        "Invalid flow definition: channel count 0 must be positive."
+    );
+
+    let mut zero_rate = flow_def;
+    let FlowDefDetails::Audio(audio) = &mut zero_rate.details else {
+        panic!("audio details expected");
+    };
+    audio.sample_rate.numerator = 0;
+    assert_eq!(
+        validation_error(&zero_rate),
+        "Invalid flow definition: rate 0/1 must be positive."
+    );
+
+    let raw = include_str!("data/nmos_raw_video_flow.json");
+    let mut empty_frame = FlowDef::from_nmos(raw).unwrap();
+    assert!(empty_frame.validate().is_ok());
+    let FlowDefDetails::Video(video) = &mut empty_frame.details else {
+        panic!("video details expected");
+    };
+    video.frame_height = 0;
+    assert_eq!(
+        validation_error(&empty_frame),
+        "Invalid flow definition: frame size 1920x0 is empty."
+    );
 }
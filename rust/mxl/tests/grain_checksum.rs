@@ -0,0 +1,22 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use mxl::OwnedGrainData;
+
+fn owned_grain(payload: &[u8]) -> OwnedGrainData {
+    OwnedGrainData {
+        payload: payload.to_vec(),
+        flags: 0,
+    }
+}
+
+#[test]
+fn crc32_matches_reference_values() {
+    // The standard CRC-32 check value.
+    assert_eq!(owned_grain(b"123456789").crc32(), 0xCBF4_3926);
+    assert_eq!(owned_grain(b"").crc32(), 0);
+    assert_eq!(
+        owned_grain(b"The quick brown fox jumps over the lazy dog").crc32(),
+        0x414F_A339
+    );
+}
@@ -0,0 +1,41 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use mxl::GrainData;
+
+#[test]
+fn rows_skip_padding_between_lines() {
+    // Three rows of 4 pixel bytes, each padded to a stride of 6 bytes, and some trailing bytes.
+    let payload: Vec<u8> = (0..3u8)
+        .flat_map(|row| [row, row, row, row, 0xFF, 0xFF])
+        .chain([0xEE; 5])
+        .collect();
+    let grain = GrainData {
+        payload: &payload,
+        total_size: payload.len(),
+        flags: 0,
+    };
+    let rows: Vec<&[u8]> = grain.rows(6, 3).unwrap().collect();
+    assert_eq!(rows.len(), 3);
+    for (index, row) in rows.iter().enumerate() {
+        assert_eq!(row.len(), 6);
+        assert_eq!(&row[..4], &[index as u8; 4]);
+    }
+}
+
+#[test]
+fn rows_are_bounds_checked() {
+    let payload = [0u8; 16];
+    let grain = GrainData {
+        payload: &payload,
+        total_size: payload.len(),
+        flags: 0,
+    };
+    assert!(grain.rows(4, 4).is_ok());
+    assert!(matches!(grain.rows(4, 5), Err(mxl::Error::InvalidArg)));
+    assert!(matches!(grain.rows(0, 4), Err(mxl::Error::InvalidArg)));
+    assert!(matches!(
+        grain.rows(usize::MAX, 2),
+        Err(mxl::Error::InvalidArg)
+    ));
+}
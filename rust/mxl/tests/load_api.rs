@@ -0,0 +1,27 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use mxl::{Error, load_api_from_candidates};
+
+#[test]
+fn load_api_from_candidates_lists_all_attempts() {
+    let candidates = [
+        PathBuf::from("/nonexistent/first/libmxl.so"),
+        PathBuf::from("/nonexistent/second/libmxl.so"),
+    ];
+    let Err(Error::Other(message)) = load_api_from_candidates(&candidates) else {
+        panic!("Loading from bogus paths must fail with an aggregated error.");
+    };
+    assert!(message.contains("/nonexistent/first/libmxl.so"));
+    assert!(message.contains("/nonexistent/second/libmxl.so"));
+}
+
+#[test]
+fn load_api_from_no_candidates_fails() {
+    assert!(matches!(
+        load_api_from_candidates(&[]),
+        Err(Error::Other(_))
+    ));
+}
@@ -0,0 +1,30 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use mxl::{FlowMetrics, Timestamp};
+use uuid::Uuid;
+
+#[test]
+fn prometheus_rendering_has_one_line_per_metric() {
+    let flow_id = Uuid::parse_str("5fbec3b1-1b0f-417d-9059-8b94a47197ed").unwrap();
+    let metrics = FlowMetrics {
+        flow_id,
+        head_index: 42,
+        last_write_time: Timestamp::from_nanos(2_000_000_000),
+        last_read_time: Timestamp::from_nanos(1_000_000_000),
+        staleness: Duration::from_millis(1500),
+    };
+    let rendered = metrics.to_prometheus();
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "mxl_flow_head_index{flow=\"5fbec3b1-1b0f-417d-9059-8b94a47197ed\"} 42",
+            "mxl_flow_last_write_time_nanoseconds{flow=\"5fbec3b1-1b0f-417d-9059-8b94a47197ed\"} 2000000000",
+            "mxl_flow_last_read_time_nanoseconds{flow=\"5fbec3b1-1b0f-417d-9059-8b94a47197ed\"} 1000000000",
+            "mxl_flow_staleness_seconds{flow=\"5fbec3b1-1b0f-417d-9059-8b94a47197ed\"} 1.5",
+        ]
+    );
+}
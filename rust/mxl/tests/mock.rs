@@ -0,0 +1,92 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "mock")]
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use mxl::{Error, GrainSink, GrainSource, Timeout, mock::MockGrainFlow};
+
+#[test]
+fn mock_reads_back_written_grains() {
+    let flow = MockGrainFlow::new(4);
+    assert_eq!(flow.head_index().unwrap(), 0);
+    flow.write_grain(10, &[1, 2, 3]).unwrap();
+    assert_eq!(flow.head_index().unwrap(), 10);
+    let grain = flow.read_grain(10, Timeout::Immediate).unwrap();
+    assert_eq!(grain.payload, [1, 2, 3]);
+}
+
+#[test]
+fn mock_out_of_range() {
+    let flow = MockGrainFlow::new(4);
+    for index in 10..=20 {
+        flow.write_grain(index, &[index as u8]).unwrap();
+    }
+    // Grains 17 to 20 are still in the ring buffer.
+    assert_eq!(
+        flow.read_grain(17, Timeout::Immediate).unwrap().payload,
+        [17]
+    );
+    assert!(matches!(
+        flow.read_grain(16, Timeout::Immediate),
+        Err(Error::OutOfRangeTooLate)
+    ));
+    assert!(matches!(
+        flow.write_grain(16, &[16]),
+        Err(Error::OutOfRangeTooLate)
+    ));
+    assert!(matches!(
+        flow.read_grain(21, Timeout::Immediate),
+        Err(Error::OutOfRangeTooEarly)
+    ));
+}
+
+#[test]
+fn mock_read_times_out() {
+    let flow = MockGrainFlow::new(4);
+    let start = Instant::now();
+    assert!(matches!(
+        flow.read_grain(1, Timeout::For(Duration::from_millis(50))),
+        Err(Error::Timeout)
+    ));
+    assert!(start.elapsed() >= Duration::from_millis(50));
+}
+
+#[test]
+fn mock_read_waits_for_writer() {
+    let flow = Arc::new(MockGrainFlow::new(4));
+    let writer = {
+        let flow = flow.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            flow.write_grain(1, &[42]).unwrap();
+        })
+    };
+    let grain = flow.read_grain(1, Timeout::Forever).unwrap();
+    assert_eq!(grain.payload, [42]);
+    writer.join().unwrap();
+}
+
+/// A pipeline stage generic over the backends: copies the grain at the head of `source` to `sink`.
+fn forward_head_grain(source: impl GrainSource, sink: impl GrainSink) -> mxl::Result<u64> {
+    let index = source.head_index()?;
+    let grain = source.read_grain(index, Timeout::For(Duration::from_secs(1)))?;
+    sink.write_grain(index, &grain.payload)?;
+    Ok(index)
+}
+
+#[test]
+fn generic_pipeline_over_mocks() {
+    let source = Arc::new(MockGrainFlow::new(4));
+    let sink = MockGrainFlow::new(4);
+    source.write_grain(7, &[7, 7]).unwrap();
+    assert_eq!(forward_head_grain(source.clone(), &sink).unwrap(), 7);
+    assert_eq!(
+        sink.read_grain(7, Timeout::Immediate).unwrap().payload,
+        [7, 7]
+    );
+}
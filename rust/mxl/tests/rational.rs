@@ -0,0 +1,118 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use mxl::{Rational, RationalExt, RationalRate};
+
+#[test]
+fn audio_rate_48k() {
+    let rate = Rational {
+        numerator: 48000,
+        denominator: 1,
+    };
+    assert_eq!(rate.as_f64(), Some(48000.0));
+    assert_eq!(rate.period(), Some(Duration::from_nanos(20_833)));
+    assert_eq!(rate.samples_in(Duration::from_millis(10)), Some(480));
+    let reduced = rate.reduce().unwrap();
+    assert_eq!((reduced.numerator, reduced.denominator), (48000, 1));
+}
+
+#[test]
+fn video_rate_29_97() {
+    let rate = Rational {
+        numerator: 30000,
+        denominator: 1001,
+    };
+    assert!((rate.as_f64().unwrap() - 29.97).abs() < 0.001);
+    assert_eq!(rate.period(), Some(Duration::from_nanos(33_366_666)));
+    assert_eq!(rate.samples_in(Duration::from_secs(1001)), Some(30000));
+    let reduced = rate.reduce().unwrap();
+    assert_eq!((reduced.numerator, reduced.denominator), (30000, 1001));
+
+    let unreduced = Rational {
+        numerator: 60000,
+        denominator: 2002,
+    };
+    let reduced = unreduced.reduce().unwrap();
+    assert_eq!((reduced.numerator, reduced.denominator), (30000, 1001));
+}
+
+#[test]
+fn zero_denominator() {
+    let rate = Rational {
+        numerator: 25,
+        denominator: 0,
+    };
+    assert_eq!(rate.as_f64(), None);
+    assert_eq!(rate.period(), None);
+    assert_eq!(rate.samples_in(Duration::from_secs(1)), None);
+    assert!(rate.reduce().is_none());
+}
+
+#[test]
+fn rate_from_f64_approx() {
+    let ntsc = RationalRate::new(30000, 1001).unwrap();
+    assert_eq!(RationalRate::from_f64_approx(30000.0 / 1001.0), Some(ntsc));
+
+    let approx = RationalRate::from_f64_approx(29.97).unwrap();
+    let ntsc_f64 = ntsc.as_rational().as_f64().unwrap();
+    assert!((approx.as_rational().as_f64().unwrap() - ntsc_f64).abs() < 0.001);
+
+    assert_eq!(
+        RationalRate::from_f64_approx(48000.0),
+        RationalRate::new(48000, 1)
+    );
+    assert_eq!(RationalRate::from_f64_approx(f64::NAN), None);
+}
+
+#[test]
+fn rate_arithmetic_reduces() {
+    let ntsc = RationalRate::new(30000, 1001).unwrap();
+    let sum = ntsc + ntsc;
+    assert_eq!((sum.numerator(), sum.denominator()), (60000, 1001));
+    let double = RationalRate::new(2, 1).unwrap() * ntsc;
+    assert_eq!(double, sum);
+    assert_eq!(sum - ntsc, ntsc);
+
+    let half = RationalRate::new(-2, -4).unwrap();
+    assert_eq!((half.numerator(), half.denominator()), (1, 2));
+    assert!(half < RationalRate::new(2, 3).unwrap());
+    assert!(ntsc < RationalRate::new(30, 1).unwrap());
+    assert!(RationalRate::new(1, 0).is_none());
+}
+
+#[test]
+fn rate_ticks_per_duration() {
+    let audio = RationalRate::new(48000, 1).unwrap();
+    assert_eq!(audio.ticks_per(Duration::from_millis(10)), 480);
+    let ntsc = RationalRate::new(30000, 1001).unwrap();
+    assert_eq!(ntsc.ticks_per(Duration::from_secs(1001)), 30000);
+    assert_eq!(
+        RationalRate::try_from(Rational {
+            numerator: 50,
+            denominator: 2
+        })
+        .unwrap(),
+        RationalRate::new(25, 1).unwrap()
+    );
+}
+
+#[test]
+fn duration_of_does_not_accumulate_rounding() {
+    let rate = Rational {
+        numerator: 30000,
+        denominator: 1001,
+    };
+    assert_eq!(
+        rate.duration_of(10),
+        Some(Duration::from_nanos(333_666_666))
+    );
+    assert_eq!(rate.duration_of(30000), Some(Duration::from_secs(1001)));
+    assert_eq!(rate.duration_of(0), Some(Duration::ZERO));
+    let invalid = Rational {
+        numerator: 0,
+        denominator: 1,
+    };
+    assert_eq!(invalid.duration_of(10), None);
+}
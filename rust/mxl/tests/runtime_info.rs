@@ -0,0 +1,50 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::{Duration, SystemTime};
+
+use mxl::{FlowRuntimeInfo, Timestamp};
+
+fn runtime_info(last_write_time: u64) -> FlowRuntimeInfo {
+    FlowRuntimeInfo::from(mxl_sys::FlowRuntimeInfo {
+        headIndex: 0,
+        lastWriteTime: last_write_time,
+        lastReadTime: 0,
+        reserved: [0; 40],
+    })
+}
+
+#[test]
+fn staleness_since_last_write() {
+    let info = runtime_info(1_000_000_000);
+    assert_eq!(
+        info.staleness(Timestamp::from_nanos(3_500_000_000)),
+        Duration::from_millis(2500)
+    );
+    assert_eq!(
+        info.staleness(Timestamp::from_nanos(500_000_000)),
+        Duration::ZERO
+    );
+}
+
+#[test]
+fn last_write_instant_from_tai_nanos() {
+    let info = runtime_info(1_500_000_000);
+    assert_eq!(
+        info.last_write_instant(),
+        SystemTime::UNIX_EPOCH + Duration::from_millis(1500)
+    );
+}
+
+#[test]
+fn timestamp_to_system_time_with_clock_offset() {
+    let timestamp = Timestamp::from_nanos(40_000_000_000);
+    assert_eq!(
+        timestamp.to_system_time(Duration::from_secs(37)),
+        SystemTime::UNIX_EPOCH + Duration::from_secs(3)
+    );
+    assert_eq!(
+        timestamp.to_system_time(Duration::from_secs(50)),
+        SystemTime::UNIX_EPOCH
+    );
+}
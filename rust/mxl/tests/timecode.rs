@@ -0,0 +1,120 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use mxl::{Rational, Timecode};
+
+const RATE_29_97: Rational = Rational {
+    numerator: 30000,
+    denominator: 1001,
+};
+const RATE_25: Rational = Rational {
+    numerator: 25,
+    denominator: 1,
+};
+
+fn timecode(hours: u32, minutes: u32, seconds: u32, frames: u32, drop_frame: bool) -> Timecode {
+    Timecode {
+        hours,
+        minutes,
+        seconds,
+        frames,
+        drop_frame,
+    }
+}
+
+#[test]
+fn one_hour_drop_frame() {
+    // An hour of drop-frame timecode skips 2 frame numbers in 54 of its 60 minutes.
+    let index = 108_000 - 2 * 54;
+    let one_hour = Timecode::from_index(index, &RATE_29_97).unwrap();
+    assert_eq!(one_hour, timecode(1, 0, 0, 0, true));
+    assert_eq!(one_hour.to_string(), "01:00:00;00");
+    assert_eq!(one_hour.to_index(&RATE_29_97).unwrap(), index);
+
+    let last_frame = Timecode::from_index(index - 1, &RATE_29_97).unwrap();
+    assert_eq!(last_frame.to_string(), "00:59:59;29");
+}
+
+#[test]
+fn drop_frame_skips_frame_numbers() {
+    // The frame after 00:00:59;29 is 00:01:00;02, but no numbers are dropped every tenth minute.
+    assert_eq!(
+        Timecode::from_index(1800, &RATE_29_97).unwrap().to_string(),
+        "00:01:00;02"
+    );
+    assert_eq!(
+        Timecode::from_index(17_982, &RATE_29_97)
+            .unwrap()
+            .to_string(),
+        "00:10:00;00"
+    );
+    assert!(matches!(
+        timecode(0, 1, 0, 1, true).to_index(&RATE_29_97),
+        Err(mxl::Error::InvalidArg)
+    ));
+    assert_eq!(
+        timecode(0, 10, 0, 1, true).to_index(&RATE_29_97).unwrap(),
+        17_983
+    );
+
+    let rate_59_94 = Rational {
+        numerator: 60000,
+        denominator: 1001,
+    };
+    assert_eq!(
+        Timecode::from_index(3600, &rate_59_94).unwrap().to_string(),
+        "00:01:00;04"
+    );
+}
+
+#[test]
+fn one_hour_non_drop_frame() {
+    let one_hour = Timecode::from_index(90_000, &RATE_25).unwrap();
+    assert_eq!(one_hour, timecode(1, 0, 0, 0, false));
+    assert_eq!(one_hour.to_string(), "01:00:00:00");
+    assert_eq!(one_hour.to_index(&RATE_25).unwrap(), 90_000);
+    assert_eq!(
+        Timecode::from_index(90_000 - 1, &RATE_25)
+            .unwrap()
+            .to_string(),
+        "00:59:59:24"
+    );
+}
+
+#[test]
+fn timecode_wraps_every_day() {
+    let frames_per_day = 25 * 86_400;
+    assert_eq!(
+        Timecode::from_index(frames_per_day + 90_000, &RATE_25).unwrap(),
+        timecode(1, 0, 0, 0, false)
+    );
+    let frames_per_day = 17_982 * 6 * 24;
+    assert_eq!(
+        Timecode::from_index(frames_per_day, &RATE_29_97).unwrap(),
+        timecode(0, 0, 0, 0, true)
+    );
+}
+
+#[test]
+fn invalid_timecodes() {
+    let rate_23_976 = Rational {
+        numerator: 24000,
+        denominator: 1001,
+    };
+    assert!(matches!(
+        Timecode::from_index(0, &rate_23_976),
+        Err(mxl::Error::Other(_))
+    ));
+    assert!(matches!(
+        timecode(1, 0, 0, 0, true).to_index(&RATE_25),
+        Err(mxl::Error::InvalidArg)
+    ));
+    assert!(matches!(
+        timecode(24, 0, 0, 0, false).to_index(&RATE_25),
+        Err(mxl::Error::InvalidArg)
+    ));
+    assert!(matches!(
+        timecode(0, 0, 0, 25, false).to_index(&RATE_25),
+        Err(mxl::Error::InvalidArg)
+    ));
+}
@@ -0,0 +1,26 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use mxl::Timeout;
+
+#[test]
+fn timeout_from_duration() {
+    assert_eq!(Timeout::from(Duration::ZERO), Timeout::Immediate);
+    assert_eq!(Timeout::from(Duration::MAX), Timeout::Forever);
+    assert_eq!(
+        Timeout::from(Duration::from_millis(10)),
+        Timeout::For(Duration::from_millis(10))
+    );
+}
+
+#[test]
+fn timeout_as_duration() {
+    assert_eq!(Timeout::Immediate.as_duration(), Duration::ZERO);
+    assert_eq!(
+        Timeout::For(Duration::from_secs(1)).as_duration(),
+        Duration::from_secs(1)
+    );
+    assert_eq!(Timeout::Forever.as_duration(), Duration::MAX);
+}
@@ -0,0 +1,58 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use mxl::WaitConfig;
+
+#[test]
+fn poll_intervals_back_off_up_to_max_poll() {
+    let wait_config = WaitConfig {
+        min_poll: Duration::from_millis(1),
+        max_poll: Duration::from_millis(10),
+        backoff: 2.0,
+    };
+    let intervals: Vec<_> = wait_config.poll_intervals().take(7).collect();
+    assert_eq!(
+        intervals,
+        [1, 2, 4, 8, 10, 10, 10].map(Duration::from_millis)
+    );
+}
+
+#[test]
+fn poll_intervals_without_backoff() {
+    let wait_config = WaitConfig {
+        min_poll: Duration::from_millis(5),
+        max_poll: Duration::from_millis(50),
+        backoff: 0.5,
+    };
+    assert!(
+        wait_config
+            .poll_intervals()
+            .take(10)
+            .all(|interval| interval == Duration::from_millis(5))
+    );
+
+    // The maximum wins over the minimum.
+    let wait_config = WaitConfig {
+        min_poll: Duration::from_millis(20),
+        max_poll: Duration::from_millis(10),
+        backoff: 2.0,
+    };
+    assert_eq!(
+        wait_config.poll_intervals().next(),
+        Some(Duration::from_millis(10))
+    );
+}
+
+#[test]
+fn zero_min_poll_still_backs_off() {
+    let wait_config = WaitConfig {
+        min_poll: Duration::ZERO,
+        max_poll: Duration::from_millis(1),
+        backoff: 2.0,
+    };
+    let intervals: Vec<_> = wait_config.poll_intervals().take(5).collect();
+    assert!(intervals.iter().all(|interval| !interval.is_zero()));
+    assert!(intervals.windows(2).all(|pair| pair[0] < pair[1]));
+}
@@ -0,0 +1,33 @@
+// SPDX-FileCopyrightText: 2026 Contributors to the Media eXchange Layer project.
+// SPDX-License-Identifier: Apache-2.0
+
+use mxl::WriterOptions;
+
+#[test]
+fn writer_options_serialization() {
+    assert_eq!(WriterOptions::new().as_option_string().unwrap(), "{}");
+    assert_eq!(
+        WriterOptions::new()
+            .max_commit_batch_size_hint(64)
+            .max_sync_batch_size_hint(256)
+            .as_option_string()
+            .unwrap(),
+        r#"{"maxCommitBatchSizeHint":64,"maxSyncBatchSizeHint":256}"#
+    );
+}
+
+#[test]
+fn invalid_writer_options() {
+    for options in [
+        WriterOptions::new().max_commit_batch_size_hint(0),
+        WriterOptions::new().max_sync_batch_size_hint(0),
+        WriterOptions::new()
+            .max_commit_batch_size_hint(64)
+            .max_sync_batch_size_hint(100),
+    ] {
+        assert!(matches!(
+            options.as_option_string(),
+            Err(mxl::Error::Other(_))
+        ));
+    }
+}